@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    operations,
+    state::{Order, OrderBookSnapshot},
+    GlobalConfig,
+};
+
+pub fn handler_activate_pending_order_update(
+    ctx: Context<ActivatePendingOrderUpdate>,
+) -> Result<()> {
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let slot = Clock::get()?.slot;
+
+    let order_before = **order;
+    operations::activate_pending_order_update(order, slot)?;
+    operations::update_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        &order_before,
+        order,
+        slot,
+    )?;
+
+    msg!(
+        "Activated pending update for order {} at slot {}",
+        ctx.accounts.order.key(),
+        slot
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ActivatePendingOrderUpdate<'info> {
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut,
+        has_one = global_config)]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(mut, has_one = global_config)]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    seeds,
+    state::{AllowedProgram, GlobalConfig},
+    utils::consts::ALLOWED_PROGRAM_SIZE,
+};
+
+pub fn handler_register_allowed_program(ctx: Context<RegisterAllowedProgram>) -> Result<()> {
+    let allowed_program = &mut ctx.accounts.allowed_program;
+    allowed_program.program_id = ctx.accounts.program.key();
+    allowed_program.enabled = 1;
+    allowed_program.bump = ctx.bumps.allowed_program;
+
+    msg!(
+        "Registered allowed program {} for global config {}",
+        ctx.accounts.program.key(),
+        ctx.accounts.global_config.key(),
+    );
+
+    Ok(())
+}
+
+pub fn handler_deregister_allowed_program(ctx: Context<DeregisterAllowedProgram>) -> Result<()> {
+    ctx.accounts.allowed_program.enabled = 0;
+
+    msg!(
+        "Deregistered allowed program {} for global config {}",
+        ctx.accounts.program.key(),
+        ctx.accounts.global_config.key(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterAllowedProgram<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(executable)]
+    pub program: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            seeds::ALLOWED_PROGRAM_SEED,
+            global_config.key().as_ref(),
+            program.key().as_ref()
+        ],
+        bump,
+        payer = admin_authority,
+        space = ALLOWED_PROGRAM_SIZE + 8
+    )]
+    pub allowed_program: Account<'info, AllowedProgram>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterAllowedProgram<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub program: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ALLOWED_PROGRAM_SEED,
+            global_config.key().as_ref(),
+            program.key().as_ref()
+        ],
+        bump,
+    )]
+    pub allowed_program: Account<'info, AllowedProgram>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::utils::oracle::{check_expected_price_within_oracle_bounds, get_pyth_price_checked};
+
+pub fn handler_assert_price_within_bounds(
+    ctx: Context<AssertPriceWithinBounds>,
+    expected_price_numerator: u64,
+    expected_price_denominator: u64,
+    tolerance_bps: u16,
+) -> Result<()> {
+    let oracle_price = get_pyth_price_checked(
+        &ctx.accounts.price_account.to_account_info(),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    check_expected_price_within_oracle_bounds(
+        &oracle_price,
+        expected_price_numerator,
+        expected_price_denominator,
+        tolerance_bps,
+    )
+}
+
+#[derive(Accounts)]
+pub struct AssertPriceWithinBounds<'info> {
+    pub price_account: AccountInfo<'info>,
+}
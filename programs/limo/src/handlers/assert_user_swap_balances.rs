@@ -1,20 +1,17 @@
-use anchor_lang::{
-    prelude::*,
-    solana_program::{
-        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
-        sysvar::instructions::get_instruction_relative,
-    },
-    Accounts, Discriminator,
-};
+use anchor_lang::{prelude::*, Accounts, Discriminator};
 use anchor_spl::token_interface::TokenAccount;
 use solana_program::sysvar::{instructions::Instructions as SysInstructions, SysvarId};
 
 use crate::{
-    instruction::{AssertUserSwapBalancesEnd, AssertUserSwapBalancesStart},
-    operations::validate_user_swap_balances,
+    check_cpi_not_allowed,
+    instruction::{
+        AssertUserSwapBalancesEnd, AssertUserSwapBalancesStart, AssertUserSwapBalancesV2End,
+        AssertUserSwapBalancesV2Start,
+    },
+    operations::{relative_user_swap_balance_bounds, validate_user_swap_balances},
     seeds,
     utils::{assert_user_swap_balance_introspection, consts::USER_SWAP_BALANCE_STATE_SIZE},
-    GetBalancesCheckedResult, LimoError, UserSwapBalancesState,
+    GetBalancesCheckedResult, UserSwapBalancesState,
 };
 
 macro_rules! get_user_balances_checked {
@@ -27,19 +24,6 @@ macro_rules! get_user_balances_checked {
     }};
 }
 
-macro_rules! check_cpi_not_allowed {
-    ($ctx:expr) => {{
-        let instruction_sysvar_account = $ctx.accounts.sysvar_instructions.to_account_info();
-        let current_ix_program_id =
-            get_instruction_relative(0, &instruction_sysvar_account)?.program_id;
-        require_keys_eq!(current_ix_program_id, crate::ID, LimoError::CPINotAllowed);
-        require!(
-            get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT,
-            LimoError::CPINotAllowed
-        );
-    }};
-}
-
 pub fn handler_assert_user_swap_balances_start(
     ctx: Context<AssertUserSwapBalancesStartContext>,
 ) -> Result<()> {
@@ -55,6 +39,7 @@ pub fn handler_assert_user_swap_balances_start(
     user_swap_balance_state.user_lamports = balances.lamports_balance;
     user_swap_balance_state.input_ta_balance = balances.input_balance;
     user_swap_balance_state.output_ta_balance = balances.output_balance;
+    user_swap_balance_state.nonce = Clock::get()?.slot;
 
     Ok(())
 }
@@ -63,6 +48,7 @@ pub fn handler_assert_user_swap_balances_end(
     ctx: Context<AssertUserSwapBalancesEndContext>,
     max_input_amount_change: u64,
     min_output_amount_change: u64,
+    max_lamports_loss: u64,
 ) -> Result<()> {
     check_cpi_not_allowed!(ctx);
     assert_user_swap_balance_introspection::ensure_start_ix_match::<AssertUserSwapBalancesStart>(
@@ -79,6 +65,59 @@ pub fn handler_assert_user_swap_balances_end(
             balances,
             max_input_amount_change,
             min_output_amount_change,
+            max_lamports_loss,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn handler_assert_user_swap_balances_v2_start(
+    ctx: Context<AssertUserSwapBalancesV2StartContext>,
+) -> Result<()> {
+    check_cpi_not_allowed!(ctx);
+    assert_user_swap_balance_introspection::ensure_end_ix_match::<AssertUserSwapBalancesV2End>(
+        &ctx.accounts.sysvar_instructions,
+        &AssertUserSwapBalancesV2Start::discriminator(),
+    )?;
+
+    let balances = get_user_balances_checked!(&ctx.accounts);
+
+    let user_swap_balance_state = &mut ctx.accounts.user_swap_balance_state.load_init()?;
+    user_swap_balance_state.user_lamports = balances.lamports_balance;
+    user_swap_balance_state.input_ta_balance = balances.input_balance;
+    user_swap_balance_state.output_ta_balance = balances.output_balance;
+    user_swap_balance_state.nonce = Clock::get()?.slot;
+
+    Ok(())
+}
+
+pub fn handler_assert_user_swap_balances_v2_end(
+    ctx: Context<AssertUserSwapBalancesV2EndContext>,
+    max_slippage_input_bps: u16,
+    min_output_improvement_bps: u16,
+) -> Result<()> {
+    check_cpi_not_allowed!(ctx);
+    assert_user_swap_balance_introspection::ensure_start_ix_match::<AssertUserSwapBalancesV2Start>(
+        &ctx.accounts.sysvar_instructions,
+        &AssertUserSwapBalancesV2End::discriminator(),
+    )?;
+
+    let balances = get_user_balances_checked!(&ctx.accounts);
+
+    {
+        let user_swap_balance_state = &mut ctx.accounts.user_swap_balance_state.load()?;
+        let (max_input_amount_change, min_output_amount_change) = relative_user_swap_balance_bounds(
+            user_swap_balance_state,
+            max_slippage_input_bps,
+            min_output_improvement_bps,
+        );
+        validate_user_swap_balances(
+            user_swap_balance_state,
+            balances,
+            max_input_amount_change,
+            min_output_amount_change,
+            u64::MAX,
         )?;
     }
 
@@ -146,3 +185,65 @@ pub struct AssertUserSwapBalancesEndContext<'info> {
     #[account(address = SysInstructions::id())]
     pub sysvar_instructions: AccountInfo<'info>,
 }
+
+#[derive(Accounts)]
+pub struct AssertUserSwapBalancesV2StartContext<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        token::authority = maker
+    )]
+    pub input_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        token::authority = maker
+    )]
+    pub output_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds = [seeds::ASSERT_SWAP_BALANCES_V2_SEED, maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = USER_SWAP_BALANCE_STATE_SIZE + 8
+    )]
+    pub user_swap_balance_state: AccountLoader<'info, UserSwapBalancesState>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = SysInstructions::id())]
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AssertUserSwapBalancesV2EndContext<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        token::authority = maker
+    )]
+    pub input_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        token::authority = maker
+    )]
+    pub output_ta: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [seeds::ASSERT_SWAP_BALANCES_V2_SEED, maker.key().as_ref()],
+        bump,
+        close = maker,
+    )]
+    pub user_swap_balance_state: AccountLoader<'info, UserSwapBalancesState>,
+
+    pub system_program: Program<'info, System>,
+
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(address = SysInstructions::id())]
+    pub sysvar_instructions: AccountInfo<'info>,
+}
@@ -0,0 +1,97 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    global_seeds, operations, seeds::GLOBAL_AUTH,
+    token_operations::lamports_transfer_from_authority_to_account,
+    utils::consts::MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS, BatchHostTipWithdrawn, GlobalConfig,
+    LimoError,
+};
+
+const ACCOUNTS_PER_CONFIG: usize = 3;
+
+pub fn handler_batch_withdraw_host_tip<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchWithdrawHostTip<'info>>,
+) -> Result<()> {
+    let config_count = ctx.remaining_accounts.len() / ACCOUNTS_PER_CONFIG;
+    require!(
+        config_count > 0
+            && config_count <= MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS
+            && config_count * ACCOUNTS_PER_CONFIG == ctx.remaining_accounts.len(),
+        LimoError::InvalidBatchSize
+    );
+
+    let admin_authority = ctx.accounts.admin_authority.key();
+    let mut amounts = [0u64; MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS];
+
+    for (idx, accounts) in ctx
+        .remaining_accounts
+        .chunks(ACCOUNTS_PER_CONFIG)
+        .enumerate()
+    {
+        let (global_config_account, pda_authority, fee_collector) =
+            (&accounts[0], &accounts[1], &accounts[2]);
+        require!(
+            ctx.remaining_accounts[..idx * ACCOUNTS_PER_CONFIG]
+                .iter()
+                .step_by(ACCOUNTS_PER_CONFIG)
+                .all(|other| other.key != global_config_account.key),
+            LimoError::InvalidAccount
+        );
+
+        let global_config_loader = AccountLoader::<GlobalConfig>::try_from(global_config_account)?;
+        let global_config = &mut global_config_loader.load_mut()?;
+
+        require_keys_eq!(
+            global_config.admin_authority,
+            admin_authority,
+            LimoError::InvalidAdminAuthority
+        );
+        require_keys_eq!(
+            global_config.pda_authority,
+            pda_authority.key(),
+            LimoError::InvalidPdaAuthority
+        );
+        require_keys_eq!(
+            global_config.effective_fee_collector(),
+            fee_collector.key(),
+            LimoError::InvalidAccount
+        );
+        require!(
+            global_config.emergency_mode == 0,
+            LimoError::EmergencyModeEnabled
+        );
+
+        let host_tip_to_withdraw =
+            operations::withdraw_host_tip(global_config, pda_authority.lamports())?;
+
+        if host_tip_to_withdraw > 0 {
+            let gc = global_config_account.key();
+            let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+            lamports_transfer_from_authority_to_account(
+                fee_collector.clone(),
+                pda_authority.clone(),
+                ctx.accounts.system_program.to_account_info(),
+                seeds,
+                host_tip_to_withdraw,
+            )?;
+        }
+
+        global_config.pda_authority_previous_lamports_balance = pda_authority.lamports();
+        amounts[idx] = host_tip_to_withdraw;
+    }
+
+    emit_cpi!(BatchHostTipWithdrawn {
+        amounts,
+        count: config_count as u8,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchWithdrawHostTip<'info> {
+    pub admin_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
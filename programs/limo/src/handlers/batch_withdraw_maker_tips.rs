@@ -0,0 +1,82 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    global_seeds, operations, seeds::GLOBAL_AUTH, state::Order,
+    token_operations::lamports_transfer_from_authority_to_account,
+    utils::consts::MAX_BATCH_WITHDRAW_MAKER_TIPS_ORDERS, BatchTipWithdrawal, GlobalConfig,
+    LimoError, TipWithdrawn,
+};
+
+pub fn handler_batch_withdraw_maker_tips<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchWithdrawMakerTips<'info>>,
+) -> Result<()> {
+    let order_count = ctx.remaining_accounts.len();
+    require!(
+        order_count > 0 && order_count <= MAX_BATCH_WITHDRAW_MAKER_TIPS_ORDERS,
+        LimoError::InvalidBatchSize
+    );
+
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let gc = ctx.accounts.global_config.key();
+    let maker = ctx.accounts.maker.key();
+
+    let mut total_lamports: u64 = 0;
+    for order_account in ctx.remaining_accounts.iter() {
+        let order_loader = AccountLoader::<Order>::try_from(order_account)?;
+        let order = &mut order_loader.load_mut()?;
+
+        require_keys_eq!(order.maker, maker, LimoError::InvalidOrderOwner);
+        require_keys_eq!(order.global_config, gc, LimoError::InvalidOrderGlobalConfig);
+
+        let tip_amount = operations::withdraw_maker_tip(order, global_config)?;
+        total_lamports = total_lamports
+            .checked_add(tip_amount)
+            .ok_or(LimoError::MathOverflow)?;
+
+        emit_cpi!(TipWithdrawn {
+            order: order_account.key(),
+            maker,
+            tip_amount,
+        });
+    }
+
+    let pda_authority_bump = global_config.pda_authority_bump as u8;
+    let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    if total_lamports > 0 {
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            total_lamports,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    emit_cpi!(BatchTipWithdrawal {
+        total_lamports,
+        order_count: order_count as u8,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchWithdrawMakerTips<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
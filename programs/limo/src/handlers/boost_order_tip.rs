@@ -0,0 +1,54 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations,
+    state::{GlobalConfig, Order},
+    token_operations::native_transfer_from_user_to_account,
+};
+
+pub fn handler_boost_order_tip(ctx: Context<BoostOrderTip>, boost_lamports: u64) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let order = &mut ctx.accounts.order.load_mut()?;
+
+    operations::boost_order_tip(order, global_config, boost_lamports)?;
+
+    native_transfer_from_user_to_account(
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        boost_lamports,
+    )?;
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    msg!(
+        "Boosted order {} by {} lamports, total boost {}",
+        ctx.accounts.order.key(),
+        boost_lamports,
+        order.tip_boost_lamports
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BoostOrderTip<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,92 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds,
+    seeds::{self, GLOBAL_AUTH},
+    token_operations::close_ata_accounts_with_signer_seeds,
+    utils::consts::MAX_BULK_CLOSE_EMPTY_VAULTS,
+    GlobalConfig, LimoError, VaultClosed,
+};
+
+pub fn handler_bulk_close_empty_vaults<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BulkCloseEmptyVaults<'info>>,
+) -> Result<()> {
+    let vault_count = ctx.remaining_accounts.len();
+    require!(
+        vault_count > 0 && vault_count <= MAX_BULK_CLOSE_EMPTY_VAULTS,
+        LimoError::InvalidBatchSize
+    );
+
+    let gc = ctx.accounts.global_config.key();
+    let pda_authority_bump = ctx.accounts.global_config.load()?.pda_authority_bump as u8;
+    let signer_seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    for vault_account in ctx.remaining_accounts.iter() {
+        require_keys_eq!(
+            *vault_account.owner,
+            ctx.accounts.token_program.key(),
+            LimoError::InvalidTokenAccountOwner
+        );
+
+        let vault = TokenAccount::try_deserialize(&mut &vault_account.data.borrow()[..])
+            .map_err(|_| LimoError::InvalidAccount)?;
+
+        let (expected_vault, _) = Pubkey::find_program_address(
+            &[seeds::ESCROW_VAULT, gc.as_ref(), vault.mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            vault_account.key(),
+            expected_vault,
+            LimoError::InvalidAccount
+        );
+
+        if vault.amount > 0 {
+            msg!(
+                "Skipping vault {}: {}",
+                vault_account.key(),
+                LimoError::VaultNotEmpty
+            );
+            continue;
+        }
+
+        close_ata_accounts_with_signer_seeds(
+            vault_account.clone(),
+            ctx.accounts.admin_authority.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        msg!(
+            "Closed vault {} for mint {}",
+            vault_account.key(),
+            vault.mint
+        );
+
+        emit_cpi!(VaultClosed {
+            vault: vault_account.key(),
+            mint: vault.mint,
+        });
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BulkCloseEmptyVaults<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        has_one = pda_authority,
+        has_one = admin_authority
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub pda_authority: AccountInfo<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,306 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    global_seeds, operations,
+    seeds::{
+        self, GLOBAL_AUTH, MAKER_ACTIVITY_SEED, MAKER_ORDER_COUNT_SEED, ORDER_SIDE_ACCOUNT_SEEDS,
+    },
+    state::{
+        MakerActivity, MakerAnalytics, MakerOrderCount, Order, OrderBookSnapshot, OrderStatus,
+        VaultEscrow,
+    },
+    token_operations::{
+        close_program_account_if_initialized, lamports_transfer_from_authority_to_account,
+        transfer_from_vault_to_token_account,
+    },
+    utils::{
+        constraints::{
+            get_token_account_checked, token_2022::validate_token_extensions, update_if_initialized,
+        },
+        consts::{MAKER_ACTIVITY_SIZE, MAKER_ORDER_COUNT_SIZE, MAX_CANCEL_ORDERS_BATCH_SIZE},
+    },
+    GlobalConfig, LimoError, OrderDisplay,
+};
+
+const ACCOUNTS_PER_ORDER: usize = 7 + ORDER_SIDE_ACCOUNT_SEEDS.len();
+
+pub fn handler_cancel_orders_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelOrdersBatch<'info>>,
+    skip_locked_orders: bool,
+) -> Result<()> {
+    let order_count = ctx.remaining_accounts.len() / ACCOUNTS_PER_ORDER;
+    require!(
+        order_count > 0
+            && order_count <= MAX_CANCEL_ORDERS_BATCH_SIZE
+            && order_count * ACCOUNTS_PER_ORDER == ctx.remaining_accounts.len(),
+        LimoError::InvalidBatchSize
+    );
+
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let gc = ctx.accounts.global_config.key();
+    let maker = ctx.accounts.maker.key();
+    let pda_authority_bump = global_config.pda_authority_bump as u8;
+    let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+    let clock = Clock::get()?;
+    let ts = u64::try_from(clock.unix_timestamp).unwrap();
+    let mut maker_analytics_state = ctx
+        .accounts
+        .maker_analytics
+        .as_ref()
+        .map(|a| a.load_mut())
+        .transpose()?;
+
+    operations::record_maker_close(global_config, &mut ctx.accounts.maker_activity, ts)?;
+
+    let mut total_lamports: u64 = 0;
+    for accounts in ctx.remaining_accounts.chunks(ACCOUNTS_PER_ORDER) {
+        let (
+            order_account,
+            input_mint_account,
+            input_vault,
+            maker_input_ata,
+            input_token_program,
+            order_book_snapshot,
+            vault_escrow,
+        ) = (
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            &accounts[4],
+            &accounts[5],
+            &accounts[6],
+        );
+        let side_accounts = &accounts[7..];
+
+        let order_loader = AccountLoader::<Order>::try_from(order_account)?;
+        {
+            let order = &mut order_loader.load_mut()?;
+
+            require_keys_eq!(order.maker, maker, LimoError::InvalidOrderOwner);
+            require_keys_eq!(order.global_config, gc, LimoError::InvalidOrderGlobalConfig);
+
+            if order.flash_ix_lock != 0 && skip_locked_orders {
+                msg!(
+                    "Skipping order {}: {}",
+                    order_account.key(),
+                    LimoError::OrderWithinFlashOperation
+                );
+                continue;
+            }
+
+            require_keys_eq!(
+                input_mint_account.key(),
+                order.input_mint,
+                LimoError::InvalidTokenMint
+            );
+            require_keys_eq!(
+                input_token_program.key(),
+                order.input_mint_program_id,
+                LimoError::InvalidAccount
+            );
+            let expected_vault = Pubkey::create_program_address(
+                &[
+                    seeds::ESCROW_VAULT,
+                    gc.as_ref(),
+                    order.input_mint.as_ref(),
+                    &[order.in_vault_bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| LimoError::InvalidAccount)?;
+            require_keys_eq!(input_vault.key(), expected_vault, LimoError::InvalidAccount);
+            let (expected_vault_escrow, _) = Pubkey::find_program_address(
+                &[
+                    seeds::VAULT_ESCROW_SEED,
+                    gc.as_ref(),
+                    order.input_mint.as_ref(),
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(
+                vault_escrow.key(),
+                expected_vault_escrow,
+                LimoError::InvalidAccount
+            );
+            get_token_account_checked(maker_input_ata, &order.input_mint, &maker)?;
+
+            let input_transfer_fee_bps =
+                validate_token_extensions(input_mint_account, vec![maker_input_ata], true, &[])?;
+            require!(
+                input_transfer_fee_bps == 0,
+                LimoError::UnsupportedTokenExtension
+            );
+
+            let (expected_order_book_snapshot, _) = Pubkey::find_program_address(
+                &[
+                    seeds::ORDER_BOOK_SEED,
+                    gc.as_ref(),
+                    order.input_mint.as_ref(),
+                    order.output_mint.as_ref(),
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(
+                order_book_snapshot.key(),
+                expected_order_book_snapshot,
+                LimoError::InvalidAccount
+            );
+            let order_book_snapshot_loader =
+                AccountLoader::<OrderBookSnapshot>::try_from(order_book_snapshot)?;
+            let order_book_snapshot_state = &mut order_book_snapshot_loader.load_mut()?;
+
+            let was_active = order.status == OrderStatus::Active as u8;
+            let was_filled = order.status == OrderStatus::Filled as u8;
+
+            operations::close_order_and_claim_tip(order, global_config, ts)?;
+            let maker_order_count = &mut ctx.accounts.maker_order_count;
+            maker_order_count.count = maker_order_count.count.saturating_sub(1);
+
+            operations::record_order_closed(
+                order,
+                was_active,
+                was_filled,
+                maker_analytics_state.as_deref_mut(),
+                order_book_snapshot_state,
+                clock.slot,
+            )?;
+
+            if order.remaining_input_amount > 0 {
+                let input_mint = InterfaceAccount::<Mint>::try_from(input_mint_account)?;
+                transfer_from_vault_to_token_account(
+                    maker_input_ata.clone(),
+                    input_vault.clone(),
+                    ctx.accounts.pda_authority.to_account_info(),
+                    input_mint_account.clone(),
+                    input_token_program.clone(),
+                    seeds,
+                    order.remaining_input_amount,
+                    input_mint.decimals,
+                )?;
+
+                update_if_initialized(vault_escrow, |vault_escrow: &mut VaultEscrow| {
+                    operations::release_vault_escrow(vault_escrow, order.remaining_input_amount)
+                })?;
+            }
+
+            total_lamports = total_lamports
+                .checked_add(order.tip_amount + order.tip_boost_lamports)
+                .ok_or(LimoError::MathOverflow)?;
+
+            let (vwap_numerator, vwap_denominator) =
+                operations::compute_vwap_fill_price(order).unwrap_or_default();
+
+            emit_cpi!(OrderDisplay {
+                initial_input_amount: order.initial_input_amount,
+                expected_output_amount: order.expected_output_amount,
+                remaining_input_amount: order.remaining_input_amount,
+                filled_output_amount: order.filled_output_amount,
+                tip_amount: order.tip_amount,
+                number_of_fills: order.number_of_fills,
+                on_event_output_amount_filled: 0,
+                on_event_tip_amount: 0,
+                order_type: order.order_type,
+                status: order.status,
+                last_updated_timestamp: order.last_updated_timestamp,
+                vwap_numerator,
+                vwap_denominator,
+                maker_note: order.maker_note_hex(),
+                fill_receipt_hash: [0; 32],
+                nonce: order.nonce,
+                tags: order.tags_hex(),
+                max_fills: order.max_fills,
+                maker: order.maker,
+                taker: Pubkey::default(),
+                input_mint: order.input_mint,
+                output_mint: order.output_mint,
+                order: order_account.key(),
+            });
+        }
+
+        close_order_side_accounts(
+            &order_account.key(),
+            side_accounts,
+            &ctx.accounts.maker.to_account_info(),
+        )?;
+        order_loader.close(ctx.accounts.maker.to_account_info())?;
+    }
+
+    if total_lamports > 0 {
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            total_lamports,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    Ok(())
+}
+
+fn close_order_side_accounts<'info>(
+    order: &Pubkey,
+    side_accounts: &[AccountInfo<'info>],
+    maker: &AccountInfo<'info>,
+) -> Result<()> {
+    for (seed, side_account) in ORDER_SIDE_ACCOUNT_SEEDS.iter().zip(side_accounts) {
+        let (expected_side_account, _) =
+            Pubkey::find_program_address(&[seed, order.as_ref()], &crate::ID);
+        require_keys_eq!(
+            side_account.key(),
+            expected_side_account,
+            LimoError::InvalidAccount
+        );
+        close_program_account_if_initialized(side_account.clone(), maker.clone())?;
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelOrdersBatch<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MAKER_ACTIVITY_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ACTIVITY_SIZE + 8
+    )]
+    pub maker_activity: Box<Account<'info, MakerActivity>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+
+    #[account(mut,
+        seeds = [seeds::MAKER_ANALYTICS_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub maker_analytics: Option<AccountLoader<'info, MakerAnalytics>>,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds,
+    seeds::{GLOBAL_AUTH, INTERMEDIARY_OUTPUT_TOKEN_ACCOUNT},
+    state::Order,
+    token_operations::{
+        close_ata_accounts_with_signer_seeds, transfer_from_vault_to_token_account,
+    },
+    GlobalConfig,
+};
+
+pub fn handler_cleanup_stuck_intermediary_account(
+    ctx: Context<CleanupStuckIntermediaryAccount>,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+
+    let pda_authority_bump = global_config.pda_authority_bump as u8;
+    let gc = ctx.accounts.global_config.key();
+    let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    let stuck_amount = ctx.accounts.intermediary_output_token_account.amount;
+    if stuck_amount > 0 {
+        transfer_from_vault_to_token_account(
+            ctx.accounts.maker_output_ata.to_account_info(),
+            ctx.accounts
+                .intermediary_output_token_account
+                .to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.output_mint.to_account_info(),
+            ctx.accounts.output_token_program.to_account_info(),
+            seeds,
+            stuck_amount,
+            ctx.accounts.output_mint.decimals,
+        )?;
+    }
+
+    close_ata_accounts_with_signer_seeds(
+        ctx.accounts
+            .intermediary_output_token_account
+            .to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.output_token_program.to_account_info(),
+        seeds,
+    )?;
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    msg!(
+        "Cleaned up intermediary account {} for order {}, returned {} to maker",
+        ctx.accounts.intermediary_output_token_account.key(),
+        ctx.accounts.order.key(),
+        stuck_amount
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CleanupStuckIntermediaryAccount<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+        has_one = admin_authority
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        has_one = global_config,
+        has_one = output_mint
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(address = order.load()?.maker)]
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = output_token_program,
+    )]
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        seeds = [INTERMEDIARY_OUTPUT_TOKEN_ACCOUNT, order.key().as_ref()],
+        bump,
+        token::mint = output_mint,
+        token::authority = pda_authority
+    )]
+    pub intermediary_output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        token::mint = output_mint,
+        token::authority = maker
+    )]
+    pub maker_output_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub output_token_program: Interface<'info, TokenInterface>,
+}
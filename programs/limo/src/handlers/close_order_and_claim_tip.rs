@@ -3,57 +3,136 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     global_seeds, operations,
-    seeds::{self, GLOBAL_AUTH},
-    state::Order,
+    seeds::{self, GLOBAL_AUTH, MAKER_ACTIVITY_SEED, MAKER_ORDER_COUNT_SEED, TEMP_WSOL_SEED},
+    state::{
+        DutchAuction, MakerActivity, MakerAnalytics, MakerOrderCount, Order, OrderBookSnapshot,
+        OrderCounterparties, OrderDelegate, OrderOutputRecipient, OrderReferrer, OrderStatus,
+        StopLimitTrigger, VaultEscrow,
+    },
+    temp_wsol_seeds,
     token_operations::{
-        lamports_transfer_from_authority_to_account, transfer_from_vault_to_token_account,
+        close_ata_accounts_with_signer_seeds,
+        initialize_intermediary_token_account_with_signer_seeds,
+        lamports_transfer_from_authority_to_account, native_transfer_from_authority_to_user,
+        transfer_from_vault_to_token_account,
+    },
+    utils::{
+        constraints::{
+            is_wsol, signer_is_maker_or_delegate, token_2022::validate_token_extensions,
+            update_if_initialized,
+        },
+        consts::{MAKER_ACTIVITY_SIZE, MAKER_ORDER_COUNT_SIZE},
     },
-    utils::constraints::token_2022::validate_token_extensions,
-    GlobalConfig, OrderDisplay,
+    GlobalConfig, LimoError, OrderDisplay,
 };
 
 pub fn handler_close_order_and_claim_tip(ctx: Context<CloseOrderAndClaimTip>) -> Result<()> {
-    validate_token_extensions(
-        &ctx.accounts.input_mint.to_account_info(),
-        vec![&ctx.accounts.maker_input_ata.to_account_info()],
-        true,
+    signer_is_maker_or_delegate(
+        &ctx.accounts.maker,
+        ctx.accounts.delegate.as_ref(),
+        ctx.accounts.order_delegate.as_deref().map(|d| &**d),
     )?;
+
+    let input_is_wsol = is_wsol(&ctx.accounts.input_mint.key());
+    let input_transfer_fee_bps =
+        if let Some(maker_input_ata) = ctx.accounts.maker_input_ata.as_ref() {
+            validate_token_extensions(
+                &ctx.accounts.input_mint.to_account_info(),
+                vec![&maker_input_ata.to_account_info()],
+                true,
+                &[],
+            )?
+        } else {
+            validate_token_extensions(
+                &ctx.accounts.input_mint.to_account_info(),
+                vec![],
+                true,
+                &[],
+            )?
+        };
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
+    let mut maker_analytics_state = ctx
+        .accounts
+        .maker_analytics
+        .as_ref()
+        .map(|a| a.load_mut())
+        .transpose()?;
+    let order_book_snapshot_state = &mut ctx.accounts.order_book_snapshot.load_mut()?;
     let order = &mut ctx.accounts.order.load_mut()?;
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
 
-    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let clock = Clock::get()?;
+    let ts = u64::try_from(clock.unix_timestamp).unwrap();
+    let was_active = order.status == OrderStatus::Active as u8;
+    let was_filled = order.status == OrderStatus::Filled as u8;
 
+    operations::record_maker_close(global_config, &mut ctx.accounts.maker_activity, ts)?;
     operations::close_order_and_claim_tip(order, global_config, ts)?;
+    let maker_order_count = &mut ctx.accounts.maker_order_count;
+    maker_order_count.count = maker_order_count.count.saturating_sub(1);
+
+    operations::record_order_closed(
+        order,
+        was_active,
+        was_filled,
+        maker_analytics_state.as_deref_mut(),
+        order_book_snapshot_state,
+        clock.slot,
+    )?;
+
     let pda_authority_bump = global_config.pda_authority_bump as u8;
     let gc = ctx.accounts.global_config.key();
     let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
 
     if order.remaining_input_amount > 0 {
-        transfer_from_vault_to_token_account(
-            ctx.accounts.maker_input_ata.to_account_info(),
-            ctx.accounts.input_vault.to_account_info(),
-            ctx.accounts.pda_authority.to_account_info(),
-            ctx.accounts.input_mint.to_account_info(),
-            ctx.accounts.input_token_program.to_account_info(),
-            seeds,
-            order.remaining_input_amount,
-            ctx.accounts.input_mint.decimals,
-        )
-        .unwrap();
+        if input_is_wsol {
+            transfer_remaining_input_as_native_sol(&ctx, seeds, order.remaining_input_amount)?;
+        } else {
+            let maker_input_ata = ctx
+                .accounts
+                .maker_input_ata
+                .as_ref()
+                .ok_or(LimoError::MakerInputAtaRequired)?;
+            transfer_from_vault_to_token_account(
+                maker_input_ata.to_account_info(),
+                ctx.accounts.input_vault.to_account_info(),
+                ctx.accounts.pda_authority.to_account_info(),
+                ctx.accounts.input_mint.to_account_info(),
+                ctx.accounts.input_token_program.to_account_info(),
+                seeds,
+                order.remaining_input_amount,
+                ctx.accounts.input_mint.decimals,
+            )
+            .unwrap();
+        }
+
+        update_if_initialized(
+            &ctx.accounts.vault_escrow,
+            |vault_escrow: &mut VaultEscrow| {
+                operations::release_vault_escrow(vault_escrow, order.remaining_input_amount)
+            },
+        )?;
     }
 
-    if order.tip_amount > 0 {
+    let maker_lamports = order.tip_amount + order.tip_boost_lamports;
+    if maker_lamports > 0 {
         lamports_transfer_from_authority_to_account(
             ctx.accounts.maker.to_account_info(),
             ctx.accounts.pda_authority.to_account_info(),
             ctx.accounts.system_program.to_account_info(),
             seeds,
-            order.tip_amount,
+            maker_lamports,
         )?;
     }
 
     global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
 
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+
     emit_cpi!(OrderDisplay {
         initial_input_amount: order.initial_input_amount,
         expected_output_amount: order.expected_output_amount,
@@ -66,6 +145,18 @@ pub fn handler_close_order_and_claim_tip(ctx: Context<CloseOrderAndClaimTip>) ->
         order_type: order.order_type,
         status: order.status,
         last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash: [0; 32],
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: Pubkey::default(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
     });
 
     Ok(())
@@ -75,7 +166,11 @@ pub fn handler_close_order_and_claim_tip(ctx: Context<CloseOrderAndClaimTip>) ->
 #[derive(Accounts)]
 pub struct CloseOrderAndClaimTip<'info> {
     #[account(mut)]
-    pub maker: Signer<'info>,
+    pub maker: AccountInfo<'info>,
+
+    /// The maker or its delegate, funds the maker PDAs when they do not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
     #[account(mut,
         has_one = maker,
@@ -106,7 +201,7 @@ pub struct CloseOrderAndClaimTip<'info> {
         token::mint = input_mint,
         token::authority = maker
     )]
-    pub maker_input_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub maker_input_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(mut,
         seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), input_mint.key().as_ref()],
@@ -116,6 +211,163 @@ pub struct CloseOrderAndClaimTip<'info> {
     )]
     pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
     pub input_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(mut,
+        seeds = [TEMP_WSOL_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub temp_wsol_token_account: Option<UncheckedAccount<'info>>,
+
+    pub rent: Option<Sysvar<'info, Rent>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MAKER_ACTIVITY_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = payer,
+        space = MAKER_ACTIVITY_SIZE + 8
+    )]
+    pub maker_activity: Box<Account<'info, MakerActivity>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = payer,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+
+    #[account(mut,
+        seeds = [seeds::REFERRER_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub order_referrer: Option<Box<Account<'info, OrderReferrer>>>,
+
+    #[account(mut,
+        seeds = [seeds::DUTCH_AUCTION_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub dutch_auction: Option<Box<Account<'info, DutchAuction>>>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    pub delegate: Option<Signer<'info>>,
+
+    #[account(mut,
+        seeds = [seeds::ORDER_DELEGATE_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub order_delegate: Option<Box<Account<'info, OrderDelegate>>>,
+
+    #[account(mut,
+        seeds = [seeds::ORDER_COUNTERPARTIES_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub order_counterparties: Option<Box<Account<'info, OrderCounterparties>>>,
+
+    #[account(mut,
+        seeds = [seeds::STOP_LIMIT_TRIGGER_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub stop_limit_trigger: Option<Box<Account<'info, StopLimitTrigger>>>,
+
+    #[account(mut,
+        seeds = [seeds::MAKER_ANALYTICS_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub maker_analytics: Option<AccountLoader<'info, MakerAnalytics>>,
+
+    #[account(mut,
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        close = maker
+    )]
+    pub order_output_recipient: Option<Box<Account<'info, OrderOutputRecipient>>>,
+}
+
+fn transfer_remaining_input_as_native_sol(
+    ctx: &Context<CloseOrderAndClaimTip>,
+    authority_signer_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let (temp_wsol_token_account, rent) = match (
+        ctx.accounts.temp_wsol_token_account.as_ref(),
+        ctx.accounts.rent.as_ref(),
+    ) {
+        (Some(temp_wsol_token_account), Some(rent)) => (temp_wsol_token_account, rent),
+        _ => return err!(LimoError::TempWsolTokenAccountRequired),
+    };
+
+    let order_key = ctx.accounts.order.key();
+    let temp_wsol_signer_seeds: &[&[u8]] =
+        temp_wsol_seeds!(ctx.bumps.temp_wsol_token_account, &order_key);
+    initialize_intermediary_token_account_with_signer_seeds(
+        temp_wsol_token_account.to_account_info(),
+        ctx.accounts.input_mint.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        rent.to_account_info(),
+        temp_wsol_signer_seeds,
+        authority_signer_seeds,
+    )?;
+
+    transfer_from_vault_to_token_account(
+        temp_wsol_token_account.to_account_info(),
+        ctx.accounts.input_vault.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.input_mint.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        authority_signer_seeds,
+        amount,
+        ctx.accounts.input_mint.decimals,
+    )?;
+
+    close_ata_accounts_with_signer_seeds(
+        temp_wsol_token_account.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        authority_signer_seeds,
+    )?;
+
+    native_transfer_from_authority_to_user(
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+        authority_signer_seeds,
+        amount,
+    )?;
+
+    Ok(())
 }
@@ -0,0 +1,64 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds,
+    seeds::{self, GLOBAL_AUTH},
+    state::GlobalConfig,
+    token_operations::close_ata_accounts_with_signer_seeds,
+    LimoError,
+};
+
+pub fn handler_close_vault(ctx: Context<CloseVault>) -> Result<()> {
+    require_eq!(ctx.accounts.vault.amount, 0, LimoError::VaultNotEmpty);
+
+    let gc = ctx.accounts.global_config.key();
+    let pda_authority_bump = ctx.accounts.global_config.load()?.pda_authority_bump as u8;
+    let signer_seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    close_ata_accounts_with_signer_seeds(
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.admin_authority.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+
+    msg!(
+        "Closed vault for global config {} with mint {}",
+        gc,
+        ctx.accounts.mint.key(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        has_one = admin_authority,
+        has_one = pda_authority @ LimoError::InvalidPdaAuthority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pda_authority,
+        token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
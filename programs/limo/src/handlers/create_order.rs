@@ -4,9 +4,17 @@ use solana_program::{program::invoke, system_instruction};
 
 use crate::{
     operations, seeds,
-    state::{GlobalConfig, Order},
-    token_operations::transfer_from_user_to_token_account,
-    utils::constraints::token_2022::validate_token_extensions,
+    state::{
+        GlobalConfig, MakerActivity, MakerAnalytics, MakerOrderCount, Order, OrderBookSnapshot,
+        OrderReferrer, VaultEscrow,
+    },
+    token_operations::{check_and_sync_native_if_needed, transfer_from_user_to_token_account},
+    utils::{
+        constraints::{token_2022::validate_token_extensions, update_if_initialized},
+        consts::{
+            FEE_TIER_PREMIUM, MAKER_ACTIVITY_SIZE, MAKER_ORDER_COUNT_SIZE, ORDER_REFERRER_SIZE,
+        },
+    },
     LimoError, OrderDisplay, OrderType,
 };
 
@@ -16,14 +24,91 @@ pub fn handler_create_order(
     output_amount: u64,
     order_type: u8,
 ) -> Result<()> {
-    validate_token_extensions(
+    create_order(ctx, input_amount, output_amount, order_type, 0, 0)
+}
+
+pub fn handler_create_order_with_slot_expiry(
+    ctx: Context<CreateOrder>,
+    input_amount: u64,
+    output_amount: u64,
+    order_type: u8,
+    expiry_slot: u64,
+) -> Result<()> {
+    check_and_sync_native_if_needed(
+        &ctx.accounts.maker_ata.to_account_info(),
+        &ctx.accounts.input_mint.to_account_info(),
+        &ctx.accounts.input_token_program.to_account_info(),
+    )?;
+
+    require!(
+        expiry_slot > Clock::get()?.slot,
+        LimoError::InvalidExpirySlot
+    );
+
+    create_order(ctx, input_amount, output_amount, order_type, expiry_slot, 0)
+}
+
+pub fn handler_create_order_with_expiry_timestamp(
+    ctx: Context<CreateOrder>,
+    input_amount: u64,
+    output_amount: u64,
+    order_type: u8,
+    expiry_timestamp: u64,
+) -> Result<()> {
+    check_and_sync_native_if_needed(
+        &ctx.accounts.maker_ata.to_account_info(),
+        &ctx.accounts.input_mint.to_account_info(),
+        &ctx.accounts.input_token_program.to_account_info(),
+    )?;
+
+    require!(
+        expiry_timestamp > u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+        LimoError::InvalidExpiryTimestamp
+    );
+
+    create_order(
+        ctx,
+        input_amount,
+        output_amount,
+        order_type,
+        0,
+        expiry_timestamp,
+    )
+}
+
+fn create_order(
+    ctx: Context<CreateOrder>,
+    input_amount: u64,
+    output_amount: u64,
+    order_type: u8,
+    expiry_slot: u64,
+    expiry_timestamp: u64,
+) -> Result<()> {
+    let input_transfer_fee_bps = validate_token_extensions(
         &ctx.accounts.input_mint.to_account_info(),
         vec![&ctx.accounts.maker_ata.to_account_info()],
         false,
+        &[],
+    )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
+    validate_token_extensions(
+        &ctx.accounts.output_mint.to_account_info(),
+        vec![],
+        false,
+        &[],
     )?;
-    validate_token_extensions(&ctx.accounts.output_mint.to_account_info(), vec![], false)?;
 
     require!(input_amount > 0, LimoError::OrderInputAmountInvalid);
+    require!(
+        ctx.accounts
+            .global_config
+            .load()?
+            .is_order_input_amount_within_bounds(input_amount),
+        LimoError::OrderInputAmountInvalid
+    );
     require!(output_amount > 0, LimoError::OrderOutputAmountInvalid);
     require!(
         ctx.accounts.input_mint.key() != ctx.accounts.output_mint.key(),
@@ -31,6 +116,22 @@ pub fn handler_create_order(
     );
     OrderType::try_from(order_type).map_err(|_| LimoError::OrderTypeInvalid)?;
 
+    let (order_close_delay_seconds, fee_tier_nft_mint) = {
+        let gc_state = ctx.accounts.global_config.load()?;
+        require!(
+            !gc_state.is_order_type_deprecated(order_type),
+            LimoError::OrderTypeDeprecated
+        );
+        require!(
+            gc_state.max_orders_per_maker == 0
+                || ctx.accounts.maker_order_count.count < gc_state.max_orders_per_maker,
+            LimoError::MakerOrderLimitReached
+        );
+        (
+            gc_state.order_close_delay_seconds,
+            gc_state.fee_tier_nft_mint,
+        )
+    };
     let order = &mut ctx.accounts.order.load_init()?;
     let clock = Clock::get()?;
 
@@ -47,8 +148,40 @@ pub fn handler_create_order(
         order_type,
         ctx.bumps.input_vault,
         clock.unix_timestamp,
+        clock.slot,
+        expiry_slot,
+        expiry_timestamp,
+        order_close_delay_seconds,
     )?;
 
+    operations::add_order_to_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        order,
+        clock.slot,
+    );
+
+    if let Some(maker_nft_ata) = ctx.accounts.maker_nft_ata.as_ref() {
+        if fee_tier_nft_mint != Pubkey::default()
+            && maker_nft_ata.mint == fee_tier_nft_mint
+            && maker_nft_ata.owner == ctx.accounts.maker.key()
+            && maker_nft_ata.amount >= 1
+        {
+            order.fee_tier = FEE_TIER_PREMIUM;
+        }
+    }
+
+    match (
+        ctx.accounts.referrer.as_ref(),
+        ctx.accounts.order_referrer.as_mut(),
+    ) {
+        (Some(referrer), Some(order_referrer)) => {
+            order_referrer.order = ctx.accounts.order.key();
+            order_referrer.referrer = referrer.key();
+        }
+        (None, None) => {}
+        _ => return err!(LimoError::InvalidAccount),
+    }
+
     transfer_from_user_to_token_account(
         ctx.accounts.maker_ata.to_account_info(),
         ctx.accounts.input_vault.to_account_info(),
@@ -59,7 +192,27 @@ pub fn handler_create_order(
         ctx.accounts.input_mint.decimals,
     )?;
 
-    let gc_state = ctx.accounts.global_config.load()?;
+    update_if_initialized(
+        &ctx.accounts.vault_escrow,
+        |vault_escrow: &mut VaultEscrow| operations::add_vault_escrow(vault_escrow, input_amount),
+    )?;
+
+    let maker_activity = &mut ctx.accounts.maker_activity;
+    maker_activity.lifetime_orders_created =
+        maker_activity.lifetime_orders_created.saturating_add(1);
+
+    let maker_order_count = &mut ctx.accounts.maker_order_count;
+    maker_order_count.count = maker_order_count.count.saturating_add(1);
+
+    if let Some(maker_analytics) = ctx.accounts.maker_analytics.as_ref() {
+        operations::record_maker_analytics_order_created(
+            &mut *maker_analytics.load_mut()?,
+            order.created_timestamp,
+        );
+    }
+
+    let mut gc_state = ctx.accounts.global_config.load_mut()?;
+    gc_state.open_order_count = gc_state.open_order_count.saturating_add(1);
     let lamports = gc_state.ata_creation_cost + gc_state.txn_fee_cost;
     drop(gc_state);
     if lamports > 0 {
@@ -86,6 +239,9 @@ pub fn handler_create_order(
         ctx.accounts.output_mint.key(),
     );
 
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+
     emit_cpi!(OrderDisplay {
         initial_input_amount: order.initial_input_amount,
         expected_output_amount: order.expected_output_amount,
@@ -98,6 +254,18 @@ pub fn handler_create_order(
         order_type: order.order_type,
         status: order.status,
         last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash: [0; 32],
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: Pubkey::default(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
     });
 
     Ok(())
@@ -142,7 +310,63 @@ pub struct CreateOrder<'info> {
     )]
     pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
     pub input_token_program: Interface<'info, TokenInterface>,
     pub output_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MAKER_ACTIVITY_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ACTIVITY_SIZE + 8
+    )]
+    pub maker_activity: Box<Account<'info, MakerActivity>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+
+    pub maker_nft_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(
+        init,
+        seeds = [seeds::REFERRER_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_REFERRER_SIZE + 8
+    )]
+    pub order_referrer: Option<Box<Account<'info, OrderReferrer>>>,
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    #[account(mut,
+        seeds = [seeds::MAKER_ANALYTICS_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub maker_analytics: Option<AccountLoader<'info, MakerAnalytics>>,
 }
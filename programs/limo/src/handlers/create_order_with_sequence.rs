@@ -0,0 +1,257 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use solana_program::{program::invoke, system_instruction};
+
+use crate::{
+    dbg_msg, operations, seeds,
+    state::{GlobalConfig, MakerOrderCount, Order, OrderBookSnapshot, VaultEscrow},
+    token_operations::transfer_from_user_to_token_account,
+    utils::{
+        constraints::{token_2022::validate_token_extensions, update_if_initialized},
+        consts::{MAKER_ORDER_COUNT_SIZE, ORDER_SIZE},
+    },
+    LimoError, OrderDisplay, OrderType,
+};
+
+pub fn handler_create_order_with_sequence(
+    ctx: Context<CreateOrderWithSequence>,
+    input_amount: u64,
+    output_amount: u64,
+    order_type: u8,
+) -> Result<()> {
+    let input_transfer_fee_bps = validate_token_extensions(
+        &ctx.accounts.input_mint.to_account_info(),
+        vec![&ctx.accounts.maker_ata.to_account_info()],
+        false,
+        &[],
+    )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
+    validate_token_extensions(
+        &ctx.accounts.output_mint.to_account_info(),
+        vec![],
+        false,
+        &[],
+    )?;
+
+    require!(input_amount > 0, LimoError::OrderInputAmountInvalid);
+    require!(
+        ctx.accounts
+            .global_config
+            .load()?
+            .is_order_input_amount_within_bounds(input_amount),
+        LimoError::OrderInputAmountInvalid
+    );
+    require!(output_amount > 0, LimoError::OrderOutputAmountInvalid);
+    require!(
+        ctx.accounts.input_mint.key() != ctx.accounts.output_mint.key(),
+        LimoError::OrderSameMint
+    );
+    OrderType::try_from(order_type).map_err(|_| LimoError::OrderTypeInvalid)?;
+
+    let order_close_delay_seconds = {
+        let gc_state = ctx.accounts.global_config.load()?;
+        require!(
+            !gc_state.is_order_type_deprecated(order_type),
+            LimoError::OrderTypeDeprecated
+        );
+        require!(
+            gc_state.max_orders_per_maker == 0
+                || ctx.accounts.maker_order_count.count < gc_state.max_orders_per_maker,
+            LimoError::MakerOrderLimitReached
+        );
+        gc_state.order_close_delay_seconds
+    };
+    let order = &mut ctx.accounts.order.load_init()?;
+    let clock = Clock::get()?;
+
+    operations::create_order(
+        order,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.maker.key(),
+        input_amount,
+        output_amount,
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        ctx.accounts.input_token_program.key(),
+        ctx.accounts.output_token_program.key(),
+        order_type,
+        ctx.bumps.input_vault,
+        clock.unix_timestamp,
+        clock.slot,
+        0,
+        0,
+        order_close_delay_seconds,
+    )?;
+
+    operations::add_order_to_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        order,
+        clock.slot,
+    );
+
+    transfer_from_user_to_token_account(
+        ctx.accounts.maker_ata.to_account_info(),
+        ctx.accounts.input_vault.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.input_mint.to_account_info(),
+        ctx.accounts.input_token_program.to_account_info(),
+        input_amount,
+        ctx.accounts.input_mint.decimals,
+    )?;
+
+    update_if_initialized(
+        &ctx.accounts.vault_escrow,
+        |vault_escrow: &mut VaultEscrow| operations::add_vault_escrow(vault_escrow, input_amount),
+    )?;
+
+    let maker_order_count = &mut ctx.accounts.maker_order_count;
+    maker_order_count.count = maker_order_count.count.saturating_add(1);
+
+    let mut gc_state = ctx.accounts.global_config.load_mut()?;
+    let sequence_number = gc_state.order_sequence;
+    gc_state.order_sequence = sequence_number
+        .checked_add(1)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    gc_state.open_order_count = gc_state.open_order_count.saturating_add(1);
+    let lamports = gc_state.ata_creation_cost + gc_state.txn_fee_cost;
+    drop(gc_state);
+    if lamports > 0 {
+        let maker = ctx.accounts.maker.key();
+        let gc = ctx.accounts.global_config.key();
+        let ixn = system_instruction::transfer(&maker, &gc, lamports);
+
+        invoke(
+            &ixn,
+            &[
+                ctx.accounts.maker.to_account_info().clone(),
+                ctx.accounts.global_config.to_account_info().clone(),
+                ctx.accounts.system_program.to_account_info().clone(),
+            ],
+        )?;
+    }
+
+    msg!(
+        "Created order {} with sequence {}, input_amount {}, input_mint {}, output_amount {}, output_mint {}",
+        ctx.accounts.order.key(),
+        sequence_number,
+        input_amount,
+        ctx.accounts.input_mint.key(),
+        output_amount,
+        ctx.accounts.output_mint.key(),
+    );
+
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+
+    emit_cpi!(OrderDisplay {
+        initial_input_amount: order.initial_input_amount,
+        expected_output_amount: order.expected_output_amount,
+        remaining_input_amount: order.remaining_input_amount,
+        filled_output_amount: order.filled_output_amount,
+        tip_amount: order.tip_amount,
+        number_of_fills: order.number_of_fills,
+        on_event_output_amount_filled: 0,
+        on_event_tip_amount: 0,
+        order_type: order.order_type,
+        status: order.status,
+        last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash: [0; 32],
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: Pubkey::default(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateOrderWithSequence<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(mut, has_one = pda_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account()]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::ORDER_SEED,
+            global_config.key().as_ref(),
+            &global_config.load()?.order_sequence.to_le_bytes()
+        ],
+        bump,
+        payer = maker,
+        space = ORDER_SIZE + 8
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        mint::token_program = input_token_program,
+    )]
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mint::token_program = output_token_program,
+    )]
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        token::mint = input_mint,
+        token::authority = maker
+    )]
+    pub maker_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pda_authority
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+}
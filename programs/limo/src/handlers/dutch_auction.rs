@@ -0,0 +1,61 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{DutchAuction, GlobalConfig, Order},
+    utils::consts::DUTCH_AUCTION_SIZE,
+};
+
+pub fn handler_initialize_dutch_auction(
+    ctx: Context<InitializeDutchAuction>,
+    start_output_amount: u64,
+    duration_seconds: u64,
+) -> Result<()> {
+    let order = &ctx.accounts.order.load()?;
+    let dutch_auction = &mut ctx.accounts.dutch_auction;
+    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    operations::initialize_dutch_auction(
+        order,
+        ctx.accounts.order.key(),
+        dutch_auction,
+        start_output_amount,
+        duration_seconds,
+        ts,
+    )?;
+
+    msg!(
+        "Started Dutch auction for order {} from {} to {} output over {}s",
+        ctx.accounts.order.key(),
+        dutch_auction.start_output_amount,
+        dutch_auction.end_output_amount,
+        duration_seconds,
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeDutchAuction<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        init,
+        seeds = [seeds::DUTCH_AUCTION_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = DUTCH_AUCTION_SIZE + 8
+    )]
+    pub dutch_auction: Box<Account<'info, DutchAuction>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -6,7 +6,7 @@ use anchor_lang::{
         instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
         sysvar::instructions::get_instruction_relative,
     },
-    Accounts,
+    Accounts, AnchorDeserialize, Discriminator,
 };
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use express_relay::{program::ExpressRelay, state::ExpressRelayMetadata};
@@ -14,35 +14,53 @@ use solana_program::sysvar::{instructions::Instructions as SysInstructions, Sysv
 
 use crate::{
     global_seeds,
-    instruction::{FlashTakeOrderEnd, FlashTakeOrderStart},
+    instruction::{
+        FlashTakeOrderEnd, FlashTakeOrderStart, FlashTakeOrderV2End, FlashTakeOrderV2Start,
+    },
     intermediary_seeds,
     operations::{
         self, flash_pay_order_output, validate_pda_authority_balance_and_update_accounting,
     },
     seeds::{self, GLOBAL_AUTH, INTERMEDIARY_OUTPUT_TOKEN_ACCOUNT},
-    state::{GlobalConfig, Order, TakeOrderEffects},
+    state::{
+        DutchAuction, GlobalConfig, MakerAnalytics, MakerVolume, MintFeeConfig, Order,
+        OrderBookSnapshot, OrderCounterparties, OrderReferrer, PairOracleConfig, StopLimitTrigger,
+        TakeOrderEffects, VaultEscrow, VwapOracle,
+    },
     token_operations::{
         close_ata_accounts_with_signer_seeds,
         initialize_intermediary_token_account_with_signer_seeds,
-        native_transfer_from_authority_to_user, native_transfer_from_user_to_account,
-        transfer_from_user_to_token_account, transfer_from_vault_to_token_account,
+        lamports_transfer_from_authority_to_account, native_transfer_from_authority_to_user,
+        native_transfer_from_user_to_account, transfer_from_user_to_token_account,
+        transfer_from_vault_to_token_account,
     },
     utils::{
         constraints::{
-            check_permission_express_relay_and_get_fees, is_counterparty_matching, is_wsol,
-            token_2022::validate_token_extensions, verify_ata,
+            check_permission_express_relay_and_get_fees, get_order_output_owner,
+            get_vwap_oracle_checked, is_counterparty_matching, is_wsol, load_if_initialized,
+            token_2022::{transfer_fee_inclusive_amount, validate_token_extensions},
+            update_if_initialized, verify_ata,
         },
         flash_ixs,
+        oracle::{
+            check_fill_price_within_oracle_bounds, check_oracle_feed_for_pair,
+            get_optional_pyth_price_checked, OraclePrice,
+        },
     },
-    LimoError, OrderDisplay,
+    FlashArgMismatch, LimoError, OrderDisplay,
 };
 
 fn handler_checks(ctx: &Context<FlashTakeOrder>) -> Result<()> {
-    validate_token_extensions(
+    let input_transfer_fee_bps = validate_token_extensions(
         &ctx.accounts.input_mint.to_account_info(),
         vec![&ctx.accounts.taker_input_ata.to_account_info()],
         false,
+        &[],
     )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
     if let Some(maker_output_ata_account) = ctx.accounts.maker_output_ata.as_ref() {
         validate_token_extensions(
             &ctx.accounts.output_mint.to_account_info(),
@@ -51,12 +69,14 @@ fn handler_checks(ctx: &Context<FlashTakeOrder>) -> Result<()> {
                 &maker_output_ata_account.to_account_info(),
             ],
             false,
+            &[],
         )?;
     } else {
         validate_token_extensions(
             &ctx.accounts.output_mint.to_account_info(),
             vec![&ctx.accounts.taker_output_ata.to_account_info()],
             false,
+            &[],
         )?;
     }
 
@@ -69,9 +89,23 @@ fn handler_checks(ctx: &Context<FlashTakeOrder>) -> Result<()> {
         LimoError::CPINotAllowed
     );
 
+    let output_owner = get_order_output_owner(
+        &ctx.accounts.order_output_recipient,
+        &ctx.accounts.maker.key(),
+    )?;
+    require!(
+        !is_wsol(&ctx.accounts.output_mint.key()) || output_owner == ctx.accounts.maker.key(),
+        LimoError::InvalidOutputRecipient
+    );
+
     if let Some(maker_output_ata_account) = ctx.accounts.maker_output_ata.as_ref() {
+        require_keys_eq!(
+            maker_output_ata_account.owner,
+            output_owner,
+            LimoError::InvalidAccount
+        );
         verify_ata(
-            &ctx.accounts.maker.key(),
+            &output_owner,
             &ctx.accounts.output_mint.key(),
             &maker_output_ata_account.key(),
             &ctx.accounts.output_token_program.key(),
@@ -86,43 +120,124 @@ fn handler_checks(ctx: &Context<FlashTakeOrder>) -> Result<()> {
     Ok(())
 }
 
+trait FlashTakeOrderIx: Discriminator + AnchorDeserialize {
+    fn flash_args(&self) -> [u64; 3];
+}
+
+macro_rules! impl_flash_take_order_ix {
+    ($($ix:ty),*) => {
+        $(impl FlashTakeOrderIx for $ix {
+            fn flash_args(&self) -> [u64; 3] {
+                [
+                    self.input_amount,
+                    self.min_output_amount,
+                    self.tip_amount_permissionless_taking,
+                ]
+            }
+        })*
+    };
+}
+
+impl_flash_take_order_ix!(
+    FlashTakeOrderStart,
+    FlashTakeOrderEnd,
+    FlashTakeOrderV2Start,
+    FlashTakeOrderV2End
+);
+
 pub fn handler_start(
     ctx: Context<FlashTakeOrder>,
     input_amount: u64,
     min_output_amount: u64,
     tip_amount_permissionless_taking: u64,
+) -> Result<()> {
+    flash_start::<FlashTakeOrderEnd>(
+        ctx,
+        input_amount,
+        min_output_amount,
+        tip_amount_permissionless_taking,
+        false,
+    )
+}
+
+pub fn handler_v2_start(
+    ctx: Context<FlashTakeOrder>,
+    input_amount: u64,
+    min_output_amount: u64,
+    tip_amount_permissionless_taking: u64,
+) -> Result<()> {
+    flash_start::<FlashTakeOrderV2End>(
+        ctx,
+        input_amount,
+        min_output_amount,
+        tip_amount_permissionless_taking,
+        true,
+    )
+}
+
+fn flash_start<T: FlashTakeOrderIx>(
+    ctx: Context<FlashTakeOrder>,
+    input_amount: u64,
+    min_output_amount: u64,
+    tip_amount_permissionless_taking: u64,
+    restrict_intermediary_ixs: bool,
 ) -> Result<()> {
     handler_checks(&ctx)?;
 
-    let pay: FlashTakeOrderEnd = flash_ixs::ensure_second_ix_match(
+    let (_, allowed_program_accounts) =
+        flash_ixs::split_allowed_program_accounts(ctx.remaining_accounts);
+    let allowed_programs = flash_ixs::get_registered_programs(
+        &ctx.accounts.global_config.key(),
+        allowed_program_accounts,
+    );
+    let pay: T = flash_ixs::ensure_second_ix_match(
         &ctx.accounts.sysvar_instructions,
         &ctx.accounts.input_mint.key(),
         &ctx.accounts.output_mint.key(),
+        ctx.accounts
+            .global_config
+            .load()?
+            .effective_max_flash_intermediary_ixs(),
+        &allowed_programs,
+        restrict_intermediary_ixs,
     )?;
 
-    require_eq!(
-        input_amount,
-        pay.input_amount,
-        LimoError::FlashIxsArgsMismatch
-    );
-    require_eq!(
-        min_output_amount,
-        pay.min_output_amount,
-        LimoError::FlashIxsArgsMismatch
-    );
-    require_eq!(
-        tip_amount_permissionless_taking,
-        pay.tip_amount_permissionless_taking,
-        LimoError::FlashIxsArgsMismatch
-    );
+    ensure_flash_args_match(
+        [
+            input_amount,
+            min_output_amount,
+            tip_amount_permissionless_taking,
+        ],
+        pay.flash_args(),
+    )?;
 
     let order = &mut ctx.accounts.order.load_mut()?;
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let clock = Clock::get()?;
+
+    let oracle_price = get_oracle_price(&ctx, clock.unix_timestamp)?;
+    check_oracle_feed_for_pair(
+        oracle_price.as_ref(),
+        ctx.accounts.pair_oracle_config.as_deref(),
+        global_config.oracle_price_tolerance_bps,
+    )?;
 
     let TakeOrderEffects {
         input_to_send_to_taker,
-        output_to_send_to_maker: _,
-    } = operations::flash_withdraw_order_input(order, input_amount, min_output_amount)?;
+        ..
+    } = operations::flash_withdraw_order_input(
+        global_config,
+        order,
+        input_amount,
+        min_output_amount,
+        clock.slot,
+        clock.unix_timestamp,
+        ctx.accounts.dutch_auction.as_deref(),
+        ctx.accounts
+            .stop_limit_trigger
+            .as_deref()
+            .zip(oracle_price.as_ref()),
+    )?;
 
     let gc = ctx.accounts.global_config.key();
     let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
@@ -138,47 +253,101 @@ pub fn handler_start(
         ctx.accounts.input_mint.decimals,
     )?;
 
+    update_if_initialized(
+        &ctx.accounts.vault_escrow,
+        |vault_escrow: &mut VaultEscrow| {
+            operations::release_vault_escrow(vault_escrow, input_to_send_to_taker)
+        },
+    )?;
+
     order.flash_start_taker_output_balance = ctx.accounts.taker_output_ata.amount;
 
     Ok(())
 }
 
-pub fn handler_end(
-    ctx: Context<FlashTakeOrder>,
+pub fn handler_end<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlashTakeOrder<'info>>,
+    input_amount: u64,
+    min_output_amount: u64,
+    tip_amount_permissionless_taking: u64,
+) -> Result<()> {
+    flash_end::<FlashTakeOrderStart>(
+        ctx,
+        input_amount,
+        min_output_amount,
+        tip_amount_permissionless_taking,
+        false,
+    )
+}
+
+pub fn handler_v2_end<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlashTakeOrder<'info>>,
+    input_amount: u64,
+    min_output_amount: u64,
+    tip_amount_permissionless_taking: u64,
+) -> Result<()> {
+    flash_end::<FlashTakeOrderV2Start>(
+        ctx,
+        input_amount,
+        min_output_amount,
+        tip_amount_permissionless_taking,
+        true,
+    )
+}
+
+fn flash_end<'info, T: FlashTakeOrderIx>(
+    ctx: Context<'_, '_, 'info, 'info, FlashTakeOrder<'info>>,
     input_amount: u64,
     min_output_amount: u64,
     tip_amount_permissionless_taking: u64,
+    restrict_intermediary_ixs: bool,
 ) -> Result<()> {
     handler_checks(&ctx)?;
 
-    let withdraw: FlashTakeOrderStart = flash_ixs::ensure_first_ix_match(
+    let (vwap_oracle_accounts, allowed_program_accounts) =
+        flash_ixs::split_allowed_program_accounts(ctx.remaining_accounts);
+    let allowed_programs = flash_ixs::get_registered_programs(
+        &ctx.accounts.global_config.key(),
+        allowed_program_accounts,
+    );
+    let withdraw: T = flash_ixs::ensure_first_ix_match(
         &ctx.accounts.sysvar_instructions,
         &ctx.accounts.input_mint.key(),
         &ctx.accounts.output_mint.key(),
+        ctx.accounts
+            .global_config
+            .load()?
+            .effective_max_flash_intermediary_ixs(),
+        &allowed_programs,
+        restrict_intermediary_ixs,
     )?;
 
-    require_eq!(
-        input_amount,
-        withdraw.input_amount,
-        LimoError::FlashIxsArgsMismatch
-    );
-    require_eq!(
-        min_output_amount,
-        withdraw.min_output_amount,
-        LimoError::FlashIxsArgsMismatch
-    );
-    require_eq!(
-        tip_amount_permissionless_taking,
-        withdraw.tip_amount_permissionless_taking,
-        LimoError::FlashIxsArgsMismatch
-    );
+    ensure_flash_args_match(
+        withdraw.flash_args(),
+        [
+            input_amount,
+            min_output_amount,
+            tip_amount_permissionless_taking,
+        ],
+    )?;
 
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
-    let is_filled_by_per = ctx.accounts.permission.is_some();
+    let is_filled_by_per =
+        ctx.accounts.permission.is_some() && global_config.express_relay_disabled == 0;
 
-    let (is_order_permissionless, order_counterparty) = {
+    let (
+        is_order_permissionless,
+        is_order_express_relay_only,
+        order_counterparty,
+        is_in_exclusive_taker_window,
+    ) = {
         let order = &ctx.accounts.order.load()?;
-        (order.permissionless != 0, order.counterparty)
+        (
+            order.permissionless != 0,
+            order.express_relay_only != 0,
+            order.counterparty,
+            order.is_in_exclusive_taker_window(Clock::get()?.unix_timestamp),
+        )
     };
 
     let tip = check_permission_and_get_tip(
@@ -186,14 +355,46 @@ pub fn handler_end(
         &order_counterparty,
         tip_amount_permissionless_taking,
         is_order_permissionless,
+        is_order_express_relay_only,
+        is_in_exclusive_taker_window,
         is_filled_by_per,
     )?;
+    operations::check_min_tip(global_config, tip, is_filled_by_per)?;
+
+    let vwap_oracle = get_vwap_oracle_checked(
+        vwap_oracle_accounts,
+        &ctx.accounts.global_config.key(),
+        &ctx.accounts.input_mint.key(),
+        &ctx.accounts.output_mint.key(),
+    )?;
+    let mut vwap_oracle_state = vwap_oracle.as_ref().map(|o| o.load_mut()).transpose()?;
+    let mint_fee_config_state = ctx
+        .accounts
+        .mint_fee_config
+        .as_ref()
+        .map(|c| c.load())
+        .transpose()?;
+    let mut maker_volume_state = ctx
+        .accounts
+        .maker_volume
+        .as_ref()
+        .map(|v| v.load_mut())
+        .transpose()?;
+    let mut maker_analytics_state = ctx
+        .accounts
+        .maker_analytics
+        .as_ref()
+        .map(|a| a.load_mut())
+        .transpose()?;
 
     let order = &mut ctx.accounts.order.load_mut()?;
+    let order_before = **order;
 
     let TakeOrderEffects {
-        input_to_send_to_taker: _,
+        input_to_send_to_taker,
         output_to_send_to_maker,
+        boost_to_send_to_taker,
+        referrer_tip_to_send,
     } = call_operations_and_get_effects(
         &ctx,
         global_config,
@@ -201,14 +402,55 @@ pub fn handler_end(
         input_amount,
         min_output_amount,
         tip,
+        vwap_oracle_state.as_deref_mut(),
+        mint_fee_config_state.as_deref(),
+        load_if_initialized::<OrderReferrer>(&ctx.accounts.order_referrer)?.is_some(),
+        maker_volume_state.as_deref_mut(),
+        maker_analytics_state.as_deref_mut(),
+    )?;
+    operations::update_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        &order_before,
+        order,
+        Clock::get()?.slot,
     )?;
 
+    if let Some(oracle_price) = get_oracle_price(&ctx, Clock::get()?.unix_timestamp)?
+        .filter(|_| global_config.oracle_price_tolerance_bps > 0)
+    {
+        check_fill_price_within_oracle_bounds(
+            &oracle_price,
+            input_to_send_to_taker,
+            ctx.accounts.input_mint.decimals,
+            output_to_send_to_maker,
+            ctx.accounts.output_mint.decimals,
+            global_config.oracle_price_tolerance_bps,
+        )?;
+    }
+
     send_output_token_amount(&ctx, global_config, output_to_send_to_maker)?;
 
-    tip_transfer_and_validation(&ctx, global_config, tip, is_filled_by_per)?;
+    tip_transfer_and_validation(
+        &ctx,
+        global_config,
+        tip,
+        boost_to_send_to_taker,
+        referrer_tip_to_send,
+        is_filled_by_per,
+    )?;
 
     order.flash_start_taker_output_balance = 0;
 
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+    let fill_receipt_hash = operations::compute_fill_receipt_hash(
+        &ctx.accounts.order.key(),
+        order.number_of_fills,
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        order.last_updated_timestamp,
+    );
+
     emit_cpi!(OrderDisplay {
         initial_input_amount: order.initial_input_amount,
         expected_output_amount: order.expected_output_amount,
@@ -221,6 +463,18 @@ pub fn handler_end(
         order_type: order.order_type,
         status: order.status,
         last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash,
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: ctx.accounts.taker.key(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
     });
 
     Ok(())
@@ -271,6 +525,13 @@ pub struct FlashTakeOrder<'info> {
     )]
     pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
     #[account(mut,
         token::mint = input_mint,
         token::authority = taker
@@ -291,7 +552,6 @@ pub struct FlashTakeOrder<'info> {
 
     #[account(mut,
         token::mint = output_mint,
-        token::authority = maker
     )]
     pub maker_output_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
@@ -315,6 +575,84 @@ pub struct FlashTakeOrder<'info> {
     pub system_program: Program<'info, System>,
 
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        seeds = [
+            seeds::MINT_FEE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_fee_config: Option<AccountLoader<'info, MintFeeConfig>>,
+
+    /// CHECK: address is verified, deserialized only when initialized
+    #[account(
+        seeds = [seeds::REFERRER_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_referrer: UncheckedAccount<'info>,
+
+    /// CHECK: address is verified, deserialized only when initialized
+    #[account(
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_output_recipient: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [seeds::DUTCH_AUCTION_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub dutch_auction: Option<Account<'info, DutchAuction>>,
+
+    pub pyth_price_account: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [
+            seeds::PAIR_ORACLE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub pair_oracle_config: Option<Account<'info, PairOracleConfig>>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    #[account(mut)]
+    pub maker_volume: Option<AccountLoader<'info, MakerVolume>>,
+
+    #[account(
+        seeds = [seeds::ORDER_COUNTERPARTIES_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub order_counterparties: Option<Box<Account<'info, OrderCounterparties>>>,
+
+    #[account(
+        seeds = [seeds::STOP_LIMIT_TRIGGER_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub stop_limit_trigger: Option<Account<'info, StopLimitTrigger>>,
+
+    #[account(mut)]
+    pub maker_analytics: Option<AccountLoader<'info, MakerAnalytics>>,
 }
 
 fn check_permission_and_get_tip(
@@ -322,20 +660,39 @@ fn check_permission_and_get_tip(
     order_counterparty: &Pubkey,
     tip_amount_permissionless_taking: u64,
     is_order_permissionless: bool,
+    is_order_express_relay_only: bool,
+    is_in_exclusive_taker_window: bool,
     is_filled_by_per: bool,
 ) -> Result<u64> {
     if !is_order_permissionless && !is_filled_by_per {
         return err!(LimoError::PermissionRequiredPermissionlessNotEnabled);
     }
 
-    if !is_counterparty_matching(order_counterparty, &ctx.accounts.taker.key()) {
+    if is_order_express_relay_only && !is_filled_by_per {
+        return err!(LimoError::PermissionRequiredPermissionlessNotEnabled);
+    }
+
+    let additional_counterparties = ctx
+        .accounts
+        .order_counterparties
+        .as_ref()
+        .map_or(&[][..], |c| &c.additional_counterparties[..]);
+    if !is_counterparty_matching(
+        order_counterparty,
+        additional_counterparties,
+        &ctx.accounts.taker.key(),
+    ) {
         return err!(LimoError::CounterpartyDisallowed);
     }
 
-    let tip = if let Some(permission_account) = ctx.accounts.permission.as_ref() {
+    if is_in_exclusive_taker_window && ctx.accounts.taker.key() != *order_counterparty {
+        return err!(LimoError::ExclusiveTakerWindowActive);
+    }
+
+    let tip = if is_filled_by_per {
         check_permission_express_relay_and_get_fees(
             &ctx.accounts.sysvar_instructions,
-            permission_account,
+            ctx.accounts.permission.as_ref().unwrap(),
             &ctx.accounts.pda_authority,
             &ctx.accounts.config_router,
             &ctx.accounts.express_relay_metadata.to_account_info(),
@@ -349,6 +706,23 @@ fn check_permission_and_get_tip(
     Ok(tip)
 }
 
+/// Field codes follow the argument order: input_amount, min_output_amount, tip
+fn ensure_flash_args_match(start_args: [u64; 3], end_args: [u64; 3]) -> Result<()> {
+    for (field, (start_value, end_value)) in start_args.into_iter().zip(end_args).enumerate() {
+        if start_value != end_value {
+            emit!(FlashArgMismatch {
+                field: field as u8,
+                start_value,
+                end_value,
+            });
+            return err!(LimoError::FlashIxsArgsMismatch);
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn call_operations_and_get_effects(
     ctx: &Context<FlashTakeOrder>,
     global_config: &mut GlobalConfig,
@@ -356,6 +730,11 @@ fn call_operations_and_get_effects(
     input_amount: u64,
     min_output_amount: u64,
     tip: u64,
+    vwap_oracle: Option<&mut VwapOracle>,
+    mint_fee_config: Option<&MintFeeConfig>,
+    has_referrer: bool,
+    maker_volume: Option<&mut MakerVolume>,
+    maker_analytics: Option<&mut MakerAnalytics>,
 ) -> Result<TakeOrderEffects> {
     let clock = Clock::get()?;
 
@@ -368,6 +747,8 @@ fn call_operations_and_get_effects(
         min(taker_output_ata_balance_diff, min_output_amount)
     };
 
+    let oracle_price = get_oracle_price(ctx, clock.unix_timestamp)?;
+
     let take_order_effects = flash_pay_order_output(
         global_config,
         order,
@@ -375,11 +756,36 @@ fn call_operations_and_get_effects(
         output_amount,
         tip,
         clock.unix_timestamp,
+        clock.slot,
+        vwap_oracle,
+        mint_fee_config,
+        has_referrer,
+        ctx.accounts.dutch_auction.as_deref(),
+        ctx.accounts
+            .stop_limit_trigger
+            .as_deref()
+            .zip(oracle_price.as_ref()),
+        maker_volume,
+        maker_analytics,
     )?;
 
     Ok(take_order_effects)
 }
 
+fn get_oracle_price(
+    ctx: &Context<FlashTakeOrder>,
+    current_timestamp: i64,
+) -> Result<Option<OraclePrice>> {
+    get_optional_pyth_price_checked(
+        ctx.accounts.pyth_price_account.as_ref(),
+        ctx.accounts
+            .stop_limit_trigger
+            .as_ref()
+            .map(|trigger| &trigger.price_feed),
+        current_timestamp,
+    )
+}
+
 fn send_output_token_amount(
     ctx: &Context<FlashTakeOrder>,
     global_config: &GlobalConfig,
@@ -417,13 +823,18 @@ fn send_output_token_amount(
             .to_account_info()
     };
 
+    let gross_output_amount = transfer_fee_inclusive_amount(
+        &ctx.accounts.output_mint.to_account_info(),
+        output_to_send_to_maker,
+        Clock::get()?.epoch,
+    )?;
     transfer_from_user_to_token_account(
         ctx.accounts.taker_output_ata.to_account_info(),
         output_destination_token_account.clone(),
         ctx.accounts.taker.to_account_info(),
         ctx.accounts.output_mint.to_account_info(),
         ctx.accounts.output_token_program.to_account_info(),
-        output_to_send_to_maker,
+        gross_output_amount,
         ctx.accounts.output_mint.decimals,
     )?;
 
@@ -450,6 +861,8 @@ fn tip_transfer_and_validation(
     ctx: &Context<FlashTakeOrder>,
     global_config: &mut GlobalConfig,
     tip: u64,
+    boost: u64,
+    referrer_tip: u64,
     is_filled_by_per: bool,
 ) -> Result<()> {
     if !is_filled_by_per {
@@ -467,5 +880,46 @@ fn tip_transfer_and_validation(
         tip,
     )?;
 
+    if referrer_tip > 0 {
+        let order_referrer = load_if_initialized::<OrderReferrer>(&ctx.accounts.order_referrer)?
+            .ok_or(LimoError::InvalidAccount)?;
+        let referrer = ctx
+            .accounts
+            .referrer
+            .as_ref()
+            .ok_or(LimoError::InvalidAccount)?;
+        require_keys_eq!(
+            referrer.key(),
+            order_referrer.referrer,
+            LimoError::InvalidAccount
+        );
+
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            referrer.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            referrer_tip,
+        )?;
+        global_config.pda_authority_previous_lamports_balance =
+            ctx.accounts.pda_authority.lamports();
+    }
+
+    if boost > 0 {
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.taker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            boost,
+        )?;
+        global_config.pda_authority_previous_lamports_balance =
+            ctx.accounts.pda_authority.lamports();
+    }
+
     Ok(())
 }
@@ -0,0 +1,71 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenInterface},
+};
+
+use crate::{
+    state::Order, token_operations::create_associated_token_account, utils::constraints::verify_ata,
+};
+
+pub fn handler_initialize_maker_output_ata(ctx: Context<InitializeMakerOutputAta>) -> Result<()> {
+    verify_ata(
+        &ctx.accounts.maker.key(),
+        &ctx.accounts.output_mint.key(),
+        &ctx.accounts.maker_output_ata.key(),
+        &ctx.accounts.output_token_program.key(),
+    )?;
+
+    if ctx.accounts.maker_output_ata.data_len() > 0 {
+        msg!(
+            "Maker output ata {} already exists",
+            ctx.accounts.maker_output_ata.key()
+        );
+        return Ok(());
+    }
+
+    create_associated_token_account(
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.maker_output_ata.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.output_mint.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.output_token_program.to_account_info(),
+        ctx.accounts.associated_token_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Created maker output ata {} for order {} paid by {}",
+        ctx.accounts.maker_output_ata.key(),
+        ctx.accounts.order.key(),
+        ctx.accounts.payer.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMakerOutputAta<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        has_one = maker,
+        has_one = output_mint,
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        mint::token_program = output_token_program,
+    )]
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub maker_output_ata: UncheckedAccount<'info>,
+
+    pub output_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,7 +1,12 @@
 use anchor_lang::{prelude::*, Accounts};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{seeds, state::GlobalConfig, LimoError};
+use crate::{
+    seeds,
+    state::{GlobalConfig, VaultEscrow},
+    utils::consts::VAULT_ESCROW_SIZE,
+    LimoError,
+};
 
 pub fn handler_initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
     msg!(
@@ -10,6 +15,10 @@ pub fn handler_initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
         ctx.accounts.mint.key(),
     );
 
+    let vault_escrow = &mut ctx.accounts.vault_escrow;
+    vault_escrow.global_config = ctx.accounts.global_config.key();
+    vault_escrow.mint = ctx.accounts.mint.key();
+
     Ok(())
 }
 
@@ -43,6 +52,15 @@ pub struct InitializeVault<'info> {
     )]
     pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        init_if_needed,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), mint.key().as_ref()],
+        bump,
+        payer = payer,
+        space = VAULT_ESCROW_SIZE + 8
+    )]
+    pub vault_escrow: Box<Account<'info, VaultEscrow>>,
+
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
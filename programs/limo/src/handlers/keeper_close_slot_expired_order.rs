@@ -0,0 +1,194 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds, operations,
+    seeds::{self, GLOBAL_AUTH},
+    state::{MakerOrderCount, Order, OrderBookSnapshot, VaultEscrow},
+    token_operations::{
+        lamports_transfer_from_authority_to_account, transfer_from_vault_to_token_account,
+    },
+    utils::{
+        constraints::{token_2022::validate_token_extensions, update_if_initialized},
+        consts::MAKER_ORDER_COUNT_SIZE,
+    },
+    GlobalConfig, LimoError, OrderDisplay,
+};
+
+pub fn handler_keeper_close_slot_expired_order(
+    ctx: Context<KeeperCloseSlotExpiredOrder>,
+) -> Result<()> {
+    let input_transfer_fee_bps = validate_token_extensions(
+        &ctx.accounts.input_mint.to_account_info(),
+        vec![&ctx.accounts.maker_input_ata.to_account_info()],
+        true,
+        &[],
+    )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+
+    let clock = Clock::get()?;
+
+    let order_before = **order;
+    operations::keeper_close_slot_expired_order(
+        order,
+        global_config,
+        clock.slot,
+        u64::try_from(clock.unix_timestamp).unwrap(),
+    )?;
+    operations::update_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        &order_before,
+        order,
+        clock.slot,
+    )?;
+    let maker_order_count = &mut ctx.accounts.maker_order_count;
+    maker_order_count.count = maker_order_count.count.saturating_sub(1);
+    let pda_authority_bump = global_config.pda_authority_bump as u8;
+    let gc = ctx.accounts.global_config.key();
+    let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    if order.remaining_input_amount > 0 {
+        transfer_from_vault_to_token_account(
+            ctx.accounts.maker_input_ata.to_account_info(),
+            ctx.accounts.input_vault.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.input_mint.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
+            seeds,
+            order.remaining_input_amount,
+            ctx.accounts.input_mint.decimals,
+        )?;
+
+        update_if_initialized(
+            &ctx.accounts.vault_escrow,
+            |vault_escrow: &mut VaultEscrow| {
+                operations::release_vault_escrow(vault_escrow, order.remaining_input_amount)
+            },
+        )?;
+    }
+
+    let maker_lamports = order.tip_amount + order.tip_boost_lamports;
+    if maker_lamports > 0 {
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            maker_lamports,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    msg!(
+        "Keeper {} closed order {} expired at slot {} timestamp {}",
+        ctx.accounts.keeper.key(),
+        ctx.accounts.order.key(),
+        order.expiry_slot,
+        order.expiry_timestamp
+    );
+
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+
+    emit_cpi!(OrderDisplay {
+        initial_input_amount: order.initial_input_amount,
+        expected_output_amount: order.expected_output_amount,
+        remaining_input_amount: order.remaining_input_amount,
+        filled_output_amount: order.filled_output_amount,
+        tip_amount: order.tip_amount,
+        number_of_fills: order.number_of_fills,
+        on_event_output_amount_filled: 0,
+        on_event_tip_amount: 0,
+        order_type: order.order_type,
+        status: order.status,
+        last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash: [0; 32],
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: Pubkey::default(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct KeeperCloseSlotExpiredOrder<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: AccountInfo<'info>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config,
+        has_one = input_mint,
+        close = maker
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = input_token_program,
+    )]
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        token::mint = input_mint,
+        token::authority = maker
+    )]
+    pub maker_input_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pda_authority
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
+    #[account(mut, has_one = global_config)]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = keeper,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+}
@@ -0,0 +1,23 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{operations, state::Order, FillRateEvent};
+
+pub fn handler_log_order_fill_rate(ctx: Context<LogOrderFillRate>) -> Result<()> {
+    let order = ctx.accounts.order.load()?;
+    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    emit_cpi!(FillRateEvent {
+        order: ctx.accounts.order.key(),
+        fill_fraction_bps: operations::order_fill_percentage_bps(&order),
+        seconds_elapsed: operations::order_age_seconds(&order, ts),
+        status: order.status,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LogOrderFillRate<'info> {
+    pub order: AccountLoader<'info, Order>,
+}
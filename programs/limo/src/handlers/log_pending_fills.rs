@@ -0,0 +1,40 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    state::Order, utils::consts::MAX_LOG_PENDING_FILLS_ORDERS, LimoError, PendingFillsStatus,
+};
+
+pub fn handler_log_pending_fills<'info>(
+    ctx: Context<'_, '_, 'info, 'info, LogPendingFills<'info>>,
+) -> Result<()> {
+    let order_count = ctx.remaining_accounts.len();
+    require!(
+        order_count > 0 && order_count <= MAX_LOG_PENDING_FILLS_ORDERS,
+        LimoError::InvalidBatchSize
+    );
+
+    let mut locked_orders = Vec::with_capacity(order_count);
+    for order_account in ctx.remaining_accounts.iter() {
+        let order_loader = AccountLoader::<Order>::try_from(order_account)?;
+        let order = order_loader.load()?;
+        if order.flash_ix_lock == 1 {
+            locked_orders.push(order_account.key());
+        }
+    }
+
+    require!(!locked_orders.is_empty(), LimoError::NoLockedOrders);
+
+    emit_cpi!(PendingFillsStatus {
+        pending_count: locked_orders.len() as u8,
+        locked_orders,
+        // Orders do not record the slot at which the flash lock was taken
+        oldest_lock_slot: 0,
+        current_slot: Clock::get()?.slot,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LogPendingFills {}
@@ -9,7 +9,8 @@ use crate::{
         constraints::get_token_account_checked, consts::USER_SWAP_BALANCE_STATE_SIZE,
         log_user_swap_balance_introspection,
     },
-    GetBalancesCheckedResult, UserSwapBalanceDiffs, UserSwapBalancesState,
+    GetBalancesCheckedResult, LimoError, SwapProgramRegistry, UserSwapBalanceDiffs,
+    UserSwapBalancesState,
 };
 
 pub fn handler_log_user_swap_balances_start(
@@ -19,12 +20,20 @@ pub fn handler_log_user_swap_balances_start(
         &ctx.accounts.sysvar_instructions,
     )?;
 
+    if let Some(swap_program_registry) = ctx.accounts.swap_program_registry.as_ref() {
+        require!(
+            swap_program_registry.is_registered(&ctx.accounts.base_accounts.swap_program_id.key()),
+            LimoError::SwapProgramNotRegistered
+        );
+    }
+
     let balances = get_balances_checked(&ctx.accounts.base_accounts)?;
 
     let user_swap_balance_state = &mut ctx.accounts.user_swap_balance_state.load_init()?;
     user_swap_balance_state.user_lamports = balances.lamports_balance;
     user_swap_balance_state.input_ta_balance = balances.input_balance;
     user_swap_balance_state.output_ta_balance = balances.output_balance;
+    user_swap_balance_state.nonce = Clock::get()?.slot;
 
     Ok(())
 }
@@ -113,6 +122,8 @@ pub struct LogUserSwapBalancesStartContext<'info> {
 
     #[account(address = SysInstructions::id())]
     pub sysvar_instructions: AccountInfo<'info>,
+
+    pub swap_program_registry: Option<Box<Account<'info, SwapProgramRegistry>>>,
 }
 
 #[event_cpi]
@@ -132,6 +143,8 @@ pub struct LogUserSwapBalancesEndContext<'info> {
 
     #[account(address = SysInstructions::id())]
     pub sysvar_instructions: AccountInfo<'info>,
+
+    pub swap_program_registry: Option<Box<Account<'info, SwapProgramRegistry>>>,
 }
 
 pub fn get_balances_checked(ctx: &LogUserSwapBalances) -> Result<GetBalancesCheckedResult> {
@@ -0,0 +1,50 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, MakerAnalytics},
+    utils::consts::MAKER_ANALYTICS_SIZE,
+};
+
+pub fn handler_initialize_maker_analytics(ctx: Context<InitializeMakerAnalytics>) -> Result<()> {
+    let maker_analytics = &mut ctx.accounts.maker_analytics.load_init()?;
+
+    operations::initialize_maker_analytics(
+        maker_analytics,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.maker.key(),
+    );
+
+    msg!(
+        "Initializing maker analytics for global config {} maker {}",
+        ctx.accounts.global_config.key(),
+        ctx.accounts.maker.key(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMakerAnalytics<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub maker: AccountInfo<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::MAKER_ANALYTICS_SEED,
+            global_config.key().as_ref(),
+            maker.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = MAKER_ANALYTICS_SIZE + 8
+    )]
+    pub maker_analytics: AccountLoader<'info, MakerAnalytics>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,126 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    global_seeds, operations,
+    seeds::{self, GLOBAL_AUTH},
+    state::{GlobalConfig, MakerVolume},
+    token_operations::lamports_transfer_from_authority_to_account,
+    utils::consts::MAKER_VOLUME_SIZE,
+};
+
+pub fn handler_initialize_maker_volume(
+    ctx: Context<InitializeMakerVolume>,
+    epoch: u64,
+) -> Result<()> {
+    let maker_volume = &mut ctx.accounts.maker_volume.load_init()?;
+
+    operations::initialize_maker_volume(
+        maker_volume,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.maker.key(),
+        epoch,
+    );
+
+    msg!(
+        "Initializing maker volume for global config {} maker {} epoch {}",
+        ctx.accounts.global_config.key(),
+        ctx.accounts.maker.key(),
+        epoch,
+    );
+
+    Ok(())
+}
+
+pub fn handler_claim_volume_rebate(ctx: Context<ClaimVolumeRebate>, _epoch: u64) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let maker_volume = &mut ctx.accounts.maker_volume.load_mut()?;
+
+    let pda_authority_balance = ctx.accounts.pda_authority.lamports();
+    let rebate = operations::claim_volume_rebate(
+        global_config,
+        maker_volume,
+        Clock::get()?.unix_timestamp,
+        pda_authority_balance,
+    )?;
+
+    let pda_authority_bump = global_config.pda_authority_bump as u8;
+    let gc = ctx.accounts.global_config.key();
+    let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+    lamports_transfer_from_authority_to_account(
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        seeds,
+        rebate,
+    )?;
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    msg!(
+        "Maker {} claimed volume rebate {} for epoch {}",
+        ctx.accounts.maker.key(),
+        rebate,
+        maker_volume.epoch
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct InitializeMakerVolume<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub maker: AccountInfo<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::MAKER_VOLUME_SEED,
+            global_config.key().as_ref(),
+            maker.key().as_ref(),
+            &epoch.to_le_bytes()
+        ],
+        bump,
+        payer = payer,
+        space = MAKER_VOLUME_SIZE + 8
+    )]
+    pub maker_volume: AccountLoader<'info, MakerVolume>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimVolumeRebate<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [
+            seeds::MAKER_VOLUME_SEED,
+            global_config.key().as_ref(),
+            maker.key().as_ref(),
+            &epoch.to_le_bytes()
+        ],
+        bump,
+        has_one = maker,
+        has_one = global_config,
+    )]
+    pub maker_volume: AccountLoader<'info, MakerVolume>,
+
+    pub system_program: Program<'info, System>,
+}
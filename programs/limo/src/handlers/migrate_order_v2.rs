@@ -0,0 +1,87 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{Order, OrderReferrer},
+    utils::consts::ORDER_REFERRER_SIZE,
+    GlobalConfig, LimoError, OrderMigrated,
+};
+
+pub fn handler_migrate_order_v2(
+    ctx: Context<MigrateOrderV2>,
+    expiry_timestamp: u64,
+    max_fills: u32,
+) -> Result<()> {
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let global_config = &ctx.accounts.global_config.load()?;
+    let current_timestamp = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    operations::migrate_order_v2(
+        order,
+        global_config,
+        expiry_timestamp,
+        max_fills,
+        current_timestamp,
+    )?;
+
+    let referrer = match (
+        ctx.accounts.referrer.as_ref(),
+        ctx.accounts.order_referrer.as_mut(),
+    ) {
+        (Some(referrer), Some(order_referrer)) => {
+            order_referrer.order = ctx.accounts.order.key();
+            order_referrer.referrer = referrer.key();
+            referrer.key()
+        }
+        (None, None) => Pubkey::default(),
+        _ => return err!(LimoError::InvalidAccount),
+    };
+
+    msg!(
+        "Order {} migrated: expiry_timestamp={} max_fills={} referrer={}",
+        ctx.accounts.order.key(),
+        order.expiry_timestamp,
+        order.max_fills,
+        referrer
+    );
+
+    emit_cpi!(OrderMigrated {
+        order: ctx.accounts.order.key(),
+        maker: ctx.accounts.maker.key(),
+        expiry_timestamp: order.expiry_timestamp,
+        max_fills: order.max_fills,
+        referrer,
+        created_timestamp: order.created_timestamp,
+        close_delay_at_creation: order.close_delay_at_creation,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateOrderV2<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config,
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(
+        init,
+        seeds = [seeds::REFERRER_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_REFERRER_SIZE + 8
+    )]
+    pub order_referrer: Option<Box<Account<'info, OrderReferrer>>>,
+
+    pub system_program: Program<'info, System>,
+}
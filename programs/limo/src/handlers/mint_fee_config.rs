@@ -0,0 +1,79 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, MintFeeConfig},
+    utils::consts::MINT_FEE_CONFIG_SIZE,
+};
+
+pub fn handler_initialize_mint_fee_config(
+    ctx: Context<InitializeMintFeeConfig>,
+    host_fee_bps: u16,
+    enabled: u8,
+) -> Result<()> {
+    let mint_fee_config = &mut ctx.accounts.mint_fee_config.load_init()?;
+
+    operations::initialize_mint_fee_config(
+        mint_fee_config,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.mint.key(),
+        host_fee_bps,
+        enabled,
+    )?;
+
+    msg!(
+        "Initializing mint fee config for global config {} with mint {}",
+        ctx.accounts.global_config.key(),
+        ctx.accounts.mint.key(),
+    );
+
+    Ok(())
+}
+
+pub fn handler_update_mint_fee_config(
+    ctx: Context<UpdateMintFeeConfig>,
+    host_fee_bps: u16,
+    enabled: u8,
+) -> Result<()> {
+    let mint_fee_config = &mut ctx.accounts.mint_fee_config.load_mut()?;
+
+    operations::update_mint_fee_config(mint_fee_config, host_fee_bps, enabled)
+}
+
+#[derive(Accounts)]
+pub struct InitializeMintFeeConfig<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::MINT_FEE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump,
+        payer = admin_authority,
+        space = MINT_FEE_CONFIG_SIZE + 8
+    )]
+    pub mint_fee_config: AccountLoader<'info, MintFeeConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMintFeeConfig<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut, has_one = global_config)]
+    pub mint_fee_config: AccountLoader<'info, MintFeeConfig>,
+}
@@ -1,25 +1,105 @@
+pub mod activate_pending_order_update;
+pub mod admin_force_cancel_order;
+pub mod allowed_program;
+pub mod assert_price_within_bounds;
 pub mod assert_user_swap_balances;
+pub mod batch_withdraw_host_tip;
+pub mod batch_withdraw_maker_tips;
+pub mod boost_order_tip;
+pub mod bulk_close_empty_vaults;
+pub mod cancel_orders_batch;
+pub mod cleanup_stuck_intermediary_account;
 pub mod close_order_and_claim_tip;
+pub mod close_vault;
 pub mod create_order;
+pub mod create_order_with_ed25519_preauth;
+pub mod create_order_with_native_sol;
+pub mod create_order_with_sequence;
+pub mod dutch_auction;
 pub mod flash_take_order;
 pub mod initialize_global_config;
+pub mod initialize_maker_output_ata;
 pub mod initialize_vault;
+pub mod initialize_vault_with_deposit;
+pub mod keeper_close_slot_expired_order;
+pub mod log_order_fill_rate;
+pub mod log_pending_fills;
 pub mod log_user_swap_balances;
+pub mod maker_analytics;
+pub mod maker_volume;
+pub mod migrate_order_v2;
+pub mod mint_fee_config;
+pub mod multisig_admin;
+pub mod order_book_snapshot;
+pub mod order_counterparties;
+pub mod order_delegate;
+pub mod order_output_recipient;
+pub mod order_transfer;
+pub mod pair_oracle_config;
+pub mod recalculate_ata_creation_cost;
+pub mod snapshot_global_config;
+pub mod split_order;
+pub mod stop_limit;
+pub mod swap_program_registry;
+pub mod sweep_dust;
 pub mod take_order;
+pub mod take_order_batch;
 pub mod update_global_config;
 pub mod update_global_config_admin;
 pub mod update_order;
+pub mod vwap_oracle;
 pub mod withdraw_host_tip;
+pub mod withdraw_maker_tip;
 
+pub use activate_pending_order_update::*;
+pub use admin_force_cancel_order::*;
+pub use allowed_program::*;
+pub use assert_price_within_bounds::*;
 pub use assert_user_swap_balances::*;
+pub use batch_withdraw_host_tip::*;
+pub use batch_withdraw_maker_tips::*;
+pub use boost_order_tip::*;
+pub use bulk_close_empty_vaults::*;
+pub use cancel_orders_batch::*;
+pub use cleanup_stuck_intermediary_account::*;
 pub use close_order_and_claim_tip::*;
+pub use close_vault::*;
 pub use create_order::*;
+pub use create_order_with_ed25519_preauth::*;
+pub use create_order_with_native_sol::*;
+pub use create_order_with_sequence::*;
+pub use dutch_auction::*;
 pub use flash_take_order::*;
 pub use initialize_global_config::*;
+pub use initialize_maker_output_ata::*;
 pub use initialize_vault::*;
+pub use initialize_vault_with_deposit::*;
+pub use keeper_close_slot_expired_order::*;
+pub use log_order_fill_rate::*;
+pub use log_pending_fills::*;
 pub use log_user_swap_balances::*;
+pub use maker_analytics::*;
+pub use maker_volume::*;
+pub use migrate_order_v2::*;
+pub use mint_fee_config::*;
+pub use multisig_admin::*;
+pub use order_book_snapshot::*;
+pub use order_counterparties::*;
+pub use order_delegate::*;
+pub use order_output_recipient::*;
+pub use order_transfer::*;
+pub use pair_oracle_config::*;
+pub use recalculate_ata_creation_cost::*;
+pub use snapshot_global_config::*;
+pub use split_order::*;
+pub use stop_limit::*;
+pub use swap_program_registry::*;
+pub use sweep_dust::*;
 pub use take_order::*;
+pub use take_order_batch::*;
 pub use update_global_config::*;
 pub use update_global_config_admin::*;
 pub use update_order::*;
+pub use vwap_oracle::*;
 pub use withdraw_host_tip::*;
+pub use withdraw_maker_tip::*;
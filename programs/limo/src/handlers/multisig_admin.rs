@@ -0,0 +1,249 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, MultiSigAdmin, Proposal, UpdateGlobalConfigMode},
+    utils::consts::{
+        MAX_MULTISIG_SIGNERS, MULTISIG_ADMIN_SIZE, PROPOSAL_SIZE,
+        UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE,
+    },
+    LimoError, ProtocolVersionUpdated,
+};
+
+pub fn handler_initialize_multisig_admin(
+    ctx: Context<InitializeMultisigAdmin>,
+    signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+    threshold: u8,
+) -> Result<()> {
+    let global_config = ctx.accounts.global_config.load()?;
+    require!(
+        global_config.multisig_enabled == 0,
+        LimoError::MultisigEnabled
+    );
+
+    let multisig_admin = &mut ctx.accounts.multisig_admin;
+    multisig_admin.global_config = ctx.accounts.global_config.key();
+    multisig_admin.bump = ctx.bumps.multisig_admin;
+    operations::set_multisig_admin_signers(multisig_admin, signers, threshold)?;
+
+    msg!(
+        "Initialized multisig admin for global config {} threshold {}",
+        ctx.accounts.global_config.key(),
+        threshold
+    );
+
+    Ok(())
+}
+
+pub fn handler_propose_global_config_update(
+    ctx: Context<ProposeGlobalConfigUpdate>,
+    mode: u16,
+    value: [u8; UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE],
+) -> Result<()> {
+    let multisig_admin_key = ctx.accounts.multisig_admin.key();
+
+    operations::propose_global_config_update(
+        &mut ctx.accounts.multisig_admin,
+        &mut ctx.accounts.proposal,
+        multisig_admin_key,
+        ctx.accounts.proposer.key(),
+        mode,
+        value,
+        u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+    )?;
+
+    msg!(
+        "Proposed global config update mode={} nonce={} by {}",
+        mode,
+        ctx.accounts.proposal.nonce,
+        ctx.accounts.proposer.key()
+    );
+
+    Ok(())
+}
+
+pub fn handler_approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let global_config = ctx.accounts.global_config.load()?;
+
+    operations::approve_proposal(
+        &global_config,
+        &ctx.accounts.multisig_admin,
+        &mut ctx.accounts.proposal,
+        &ctx.accounts.signer.key(),
+        u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+    )?;
+
+    msg!(
+        "Proposal nonce={} approved by {} approvals={:#07b}",
+        ctx.accounts.proposal.nonce,
+        ctx.accounts.signer.key(),
+        ctx.accounts.proposal.approvals
+    );
+
+    Ok(())
+}
+
+pub fn handler_execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+    let prev_protocol_version = global_config.protocol_version;
+
+    operations::multisig_signer_index(&ctx.accounts.multisig_admin, &ctx.accounts.signer.key())?;
+    operations::execute_proposal(
+        global_config,
+        &mut ctx.accounts.multisig_admin,
+        &ctx.accounts.proposal,
+        ts,
+    )?;
+
+    if ctx.accounts.proposal.mode == UpdateGlobalConfigMode::UpdateProtocolVersion as u16 {
+        emit_cpi!(ProtocolVersionUpdated {
+            old: prev_protocol_version,
+            new: global_config.protocol_version,
+            ts,
+        });
+    }
+
+    msg!(
+        "Executed proposal nonce={} mode={}",
+        ctx.accounts.proposal.nonce,
+        ctx.accounts.proposal.mode
+    );
+
+    Ok(())
+}
+
+pub fn handler_close_expired_proposal(ctx: Context<CloseExpiredProposal>) -> Result<()> {
+    let global_config = ctx.accounts.global_config.load()?;
+
+    require!(
+        operations::is_proposal_expired(
+            &global_config,
+            &ctx.accounts.proposal,
+            u64::try_from(Clock::get()?.unix_timestamp).unwrap(),
+        ),
+        LimoError::ProposalNotExpired
+    );
+
+    msg!(
+        "Closed expired proposal nonce={}",
+        ctx.accounts.proposal.nonce
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMultisigAdmin<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump,
+        payer = admin_authority,
+        space = MULTISIG_ADMIN_SIZE + 8
+    )]
+    pub multisig_admin: Account<'info, MultiSigAdmin>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeGlobalConfigUpdate<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut,
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump = multisig_admin.bump,
+        has_one = global_config
+    )]
+    pub multisig_admin: Account<'info, MultiSigAdmin>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::PROPOSAL_SEED,
+            multisig_admin.key().as_ref(),
+            &multisig_admin.nonce.to_le_bytes()
+        ],
+        bump,
+        payer = proposer,
+        space = PROPOSAL_SIZE + 8
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump = multisig_admin.bump,
+        has_one = global_config
+    )]
+    pub multisig_admin: Account<'info, MultiSigAdmin>,
+
+    #[account(mut, has_one = multisig_admin)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut,
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump = multisig_admin.bump,
+        has_one = global_config
+    )]
+    pub multisig_admin: Account<'info, MultiSigAdmin>,
+
+    #[account(mut,
+        has_one = multisig_admin,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseExpiredProposal<'info> {
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump = multisig_admin.bump,
+        has_one = global_config
+    )]
+    pub multisig_admin: Account<'info, MultiSigAdmin>,
+
+    #[account(mut,
+        has_one = multisig_admin,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, Order, OrderBookSnapshot, OrderStatus},
+    utils::consts::ORDER_BOOK_SNAPSHOT_SIZE,
+    LimoError,
+};
+
+pub fn handler_initialize_order_book_snapshot(
+    ctx: Context<InitializeOrderBookSnapshot>,
+) -> Result<()> {
+    let order_book_snapshot = &mut ctx.accounts.order_book_snapshot.load_init()?;
+
+    operations::initialize_order_book_snapshot(
+        order_book_snapshot,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        Clock::get()?.slot,
+    );
+
+    msg!(
+        "Initializing order book snapshot for global config {} with input mint {}, output mint {}",
+        ctx.accounts.global_config.key(),
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+    );
+
+    Ok(())
+}
+
+pub fn handler_update_order_book_snapshot<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateOrderBookSnapshot<'info>>,
+) -> Result<()> {
+    let order_book_snapshot = &mut ctx.accounts.order_book_snapshot.load_mut()?;
+    let slot = Clock::get()?.slot;
+
+    for (idx, order_account) in ctx.remaining_accounts.iter().enumerate() {
+        require!(
+            ctx.remaining_accounts[..idx]
+                .iter()
+                .all(|other| other.key != order_account.key),
+            LimoError::InvalidAccount
+        );
+    }
+
+    // Creates, fills and closes keep the totals current, this rebuilds them
+    // from the full set of active orders, e.g. to count orders that predate it
+    operations::reset_order_book_snapshot(order_book_snapshot, slot);
+
+    for order_account in ctx.remaining_accounts.iter() {
+        let order_loader = AccountLoader::<Order>::try_from(order_account)?;
+        let order = order_loader.load()?;
+
+        require!(
+            order.global_config == order_book_snapshot.global_config
+                && order.input_mint == order_book_snapshot.input_mint
+                && order.output_mint == order_book_snapshot.output_mint,
+            LimoError::InvalidAccount
+        );
+
+        if order.status == OrderStatus::Active as u8 {
+            operations::add_order_to_order_book_snapshot(order_book_snapshot, &order, slot);
+        }
+    }
+
+    msg!(
+        "Order book snapshot updated active_order_count={} total_remaining_input={} total_expected_output={}",
+        order_book_snapshot.active_order_count,
+        order_book_snapshot.total_remaining_input(),
+        order_book_snapshot.total_expected_output(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeOrderBookSnapshot<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = ORDER_BOOK_SNAPSHOT_SIZE + 8
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateOrderBookSnapshot<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut, has_one = global_config)]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    seeds,
+    state::{Order, OrderCounterparties},
+    utils::consts::ORDER_COUNTERPARTIES_SIZE,
+};
+
+pub fn handler_initialize_order_counterparties(
+    ctx: Context<InitializeOrderCounterparties>,
+) -> Result<()> {
+    let order_counterparties = &mut ctx.accounts.order_counterparties;
+    order_counterparties.order = ctx.accounts.order.key();
+
+    msg!(
+        "Initialized counterparties account for order {}",
+        ctx.accounts.order.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeOrderCounterparties<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(has_one = maker)]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        init,
+        seeds = [seeds::ORDER_COUNTERPARTIES_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_COUNTERPARTIES_SIZE + 8
+    )]
+    pub order_counterparties: Box<Account<'info, OrderCounterparties>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    seeds,
+    state::{Order, OrderDelegate},
+    utils::consts::ORDER_DELEGATE_SIZE,
+    DelegateSet,
+};
+
+pub fn handler_set_order_delegate(ctx: Context<SetOrderDelegate>, delegate: Pubkey) -> Result<()> {
+    let order_delegate = &mut ctx.accounts.order_delegate;
+    order_delegate.order = ctx.accounts.order.key();
+    order_delegate.delegate = delegate;
+
+    msg!(
+        "Order {} delegate set to {}",
+        ctx.accounts.order.key(),
+        delegate
+    );
+
+    emit_cpi!(DelegateSet {
+        order: ctx.accounts.order.key(),
+        maker: ctx.accounts.maker.key(),
+        delegate,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetOrderDelegate<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(has_one = maker)]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::ORDER_DELEGATE_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_DELEGATE_SIZE + 8
+    )]
+    pub order_delegate: Box<Account<'info, OrderDelegate>>,
+
+    pub system_program: Program<'info, System>,
+}
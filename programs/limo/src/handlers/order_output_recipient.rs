@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    seeds,
+    state::{GlobalConfig, Order, OrderOutputRecipient, OrderStatus},
+    utils::consts::ORDER_OUTPUT_RECIPIENT_SIZE,
+    LimoError,
+};
+
+pub fn handler_set_order_output_recipient(
+    ctx: Context<SetOrderOutputRecipient>,
+    recipient: Pubkey,
+) -> Result<()> {
+    {
+        let order = ctx.accounts.order.load()?;
+        require!(
+            order.status == OrderStatus::Active as u8 && order.number_of_fills == 0,
+            LimoError::OrderNotActive
+        );
+    }
+    require!(
+        recipient != Pubkey::default(),
+        LimoError::InvalidOutputRecipient
+    );
+
+    let order_output_recipient = &mut ctx.accounts.order_output_recipient;
+    order_output_recipient.order = ctx.accounts.order.key();
+    order_output_recipient.recipient = recipient;
+
+    msg!(
+        "Order {} output recipient set to {}",
+        ctx.accounts.order.key(),
+        recipient
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetOrderOutputRecipient<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        init,
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_OUTPUT_RECIPIENT_SIZE + 8
+    )]
+    pub order_output_recipient: Box<Account<'info, OrderOutputRecipient>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,245 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    global_seeds, operations,
+    seeds::{self, GLOBAL_AUTH},
+    state::{GlobalConfig, Order, OrderTransferProposal},
+    token_operations::{
+        close_program_account_if_initialized, lamports_transfer_from_authority_to_account,
+    },
+    utils::consts::ORDER_TRANSFER_PROPOSAL_SIZE,
+    LimoError, OrderTransferred,
+};
+
+pub fn handler_propose_order_transfer(ctx: Context<ProposeOrderTransfer>) -> Result<()> {
+    let order = &ctx.accounts.order.load()?;
+    let proposal = &mut ctx.accounts.order_transfer_proposal;
+    let clock = Clock::get()?;
+
+    operations::propose_order_transfer(
+        order,
+        ctx.accounts.order.key(),
+        proposal,
+        ctx.accounts.new_maker.key(),
+        clock.unix_timestamp as u64,
+    )?;
+
+    msg!(
+        "Proposed transfer of order {} from {} to {}",
+        ctx.accounts.order.key(),
+        order.maker,
+        proposal.pending_new_maker
+    );
+
+    Ok(())
+}
+
+pub fn handler_accept_order_transfer(ctx: Context<AcceptOrderTransfer>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let clock = Clock::get()?;
+
+    let previous_maker_tip_amount = operations::accept_order_transfer(
+        order,
+        global_config,
+        &ctx.accounts.order_transfer_proposal,
+        clock.unix_timestamp as u64,
+    )?;
+
+    close_program_account_if_initialized(
+        ctx.accounts.order_delegate.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+    )?;
+    close_program_account_if_initialized(
+        ctx.accounts.order_output_recipient.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+    )?;
+
+    if previous_maker_tip_amount > 0 {
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            previous_maker_tip_amount,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    msg!(
+        "Transferred order {} from {} to {}, settled tip {}",
+        ctx.accounts.order.key(),
+        ctx.accounts.maker.key(),
+        order.maker,
+        previous_maker_tip_amount
+    );
+
+    Ok(())
+}
+
+pub fn handler_transfer_order(ctx: Context<TransferOrder>) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let clock = Clock::get()?;
+
+    let previous_maker_tip_amount = operations::transfer_order(
+        order,
+        global_config,
+        ctx.accounts.new_maker.key(),
+        clock.unix_timestamp as u64,
+    )?;
+
+    close_program_account_if_initialized(
+        ctx.accounts.order_delegate.to_account_info(),
+        ctx.accounts.current_maker.to_account_info(),
+    )?;
+    close_program_account_if_initialized(
+        ctx.accounts.order_output_recipient.to_account_info(),
+        ctx.accounts.current_maker.to_account_info(),
+    )?;
+
+    if previous_maker_tip_amount > 0 {
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.current_maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            previous_maker_tip_amount,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    emit_cpi!(OrderTransferred {
+        order: ctx.accounts.order.key(),
+        old_maker: ctx.accounts.current_maker.key(),
+        new_maker: order.maker,
+        remaining_input_amount: order.remaining_input_amount,
+        expected_output_amount: order.expected_output_amount,
+        filled_output_amount: order.filled_output_amount,
+        status: order.status,
+        last_updated_timestamp: order.last_updated_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeOrderTransfer<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    pub new_maker: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::ORDER_TRANSFER_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = ORDER_TRANSFER_PROPOSAL_SIZE + 8
+    )]
+    pub order_transfer_proposal: Box<Account<'info, OrderTransferProposal>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOrderTransfer<'info> {
+    pub new_maker: Signer<'info>,
+
+    #[account(mut)]
+    pub maker: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(mut,
+        seeds = [seeds::ORDER_TRANSFER_SEED, order.key().as_ref()],
+        bump,
+        has_one = order,
+        constraint = order_transfer_proposal.pending_new_maker == new_maker.key(),
+        close = maker
+    )]
+    pub order_transfer_proposal: Box<Account<'info, OrderTransferProposal>>,
+
+    /// CHECK: address is verified, closed to the previous maker when initialized
+    #[account(mut,
+        seeds = [seeds::ORDER_DELEGATE_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: address is verified, closed to the previous maker when initialized
+    #[account(mut,
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_output_recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferOrder<'info> {
+    #[account(mut)]
+    pub current_maker: Signer<'info>,
+
+    pub new_maker: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(mut,
+        has_one = global_config,
+        constraint = order.load()?.maker == current_maker.key() @ LimoError::InvalidOrderOwner
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    /// CHECK: address is verified, closed to the previous maker when initialized
+    #[account(mut,
+        seeds = [seeds::ORDER_DELEGATE_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_delegate: UncheckedAccount<'info>,
+
+    /// CHECK: address is verified, closed to the previous maker when initialized
+    #[account(mut,
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_output_recipient: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    seeds,
+    state::{GlobalConfig, PairOracleConfig},
+    utils::consts::PAIR_ORACLE_CONFIG_SIZE,
+    LimoError,
+};
+
+pub fn handler_set_pair_oracle_config(
+    ctx: Context<SetPairOracleConfig>,
+    price_feed_id: [u8; 32],
+) -> Result<()> {
+    require!(
+        price_feed_id != [0; 32],
+        LimoError::InvalidOraclePriceAccount
+    );
+
+    let pair_oracle_config = &mut ctx.accounts.pair_oracle_config;
+    pair_oracle_config.global_config = ctx.accounts.global_config.key();
+    pair_oracle_config.input_mint = ctx.accounts.input_mint.key();
+    pair_oracle_config.output_mint = ctx.accounts.output_mint.key();
+    pair_oracle_config.price_feed_id = price_feed_id;
+
+    msg!(
+        "Set oracle price feed for pair {} -> {} on global config {}",
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        ctx.accounts.global_config.key(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPairOracleConfig<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            seeds::PAIR_ORACLE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump,
+        payer = admin_authority,
+        space = PAIR_ORACLE_CONFIG_SIZE + 8
+    )]
+    pub pair_oracle_config: Box<Account<'info, PairOracleConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
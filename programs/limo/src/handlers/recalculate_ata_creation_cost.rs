@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::spl_token_2022::{
+    extension::ExtensionType, state::Account as Token2022Account,
+};
+
+use crate::{CostRecalculated, GlobalConfig};
+
+pub fn handler_recalculate_ata_creation_cost(
+    ctx: Context<RecalculateAtaCreationCost>,
+) -> Result<()> {
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let rent = Rent::get()?;
+
+    let token_account_len = anchor_spl::token::TokenAccount::LEN;
+    let token_2022_account_len = ExtensionType::try_calculate_account_len::<Token2022Account>(&[
+        ExtensionType::ImmutableOwner,
+    ])?;
+    let new_ata_creation_cost = rent
+        .minimum_balance(token_account_len)
+        .max(rent.minimum_balance(token_2022_account_len));
+
+    let new_txn_fee_cost = if global_config.base_fee_lamports > 0 {
+        global_config.base_fee_lamports
+    } else {
+        global_config.txn_fee_cost
+    };
+
+    let old_ata_creation_cost = global_config.ata_creation_cost;
+    let old_txn_fee_cost = global_config.txn_fee_cost;
+    global_config.ata_creation_cost = new_ata_creation_cost;
+    global_config.txn_fee_cost = new_txn_fee_cost;
+
+    msg!(
+        "Recalculated costs ata_creation_cost new={} prev={} txn_fee_cost new={} prev={}",
+        new_ata_creation_cost,
+        old_ata_creation_cost,
+        new_txn_fee_cost,
+        old_txn_fee_cost
+    );
+
+    emit_cpi!(CostRecalculated {
+        old_ata_creation_cost,
+        new_ata_creation_cost,
+        old_txn_fee_cost,
+        new_txn_fee_cost,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecalculateAtaCreationCost<'info> {
+    #[account(mut)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+}
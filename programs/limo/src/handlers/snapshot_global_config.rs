@@ -0,0 +1,35 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{GlobalConfig, GlobalConfigSnapshot};
+
+pub fn handler_snapshot_global_config(ctx: Context<SnapshotGlobalConfig>) -> Result<()> {
+    let global_config = ctx.accounts.global_config.load()?;
+    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    emit_cpi!(GlobalConfigSnapshot {
+        emergency_mode: global_config.emergency_mode,
+        flash_blocked: global_config.flash_take_order_blocked,
+        new_orders_blocked: global_config.new_orders_blocked,
+        taking_blocked: global_config.orders_taking_blocked,
+        host_fee_bps: global_config.host_fee_bps,
+        order_close_delay_seconds: global_config.order_close_delay_seconds,
+        total_tip_amount: global_config.total_tip_amount,
+        host_tip_amount: global_config.host_tip_amount,
+        txn_fee_cost: global_config.txn_fee_cost,
+        ata_creation_cost: global_config.ata_creation_cost,
+        open_order_count: global_config.open_order_count,
+        total_volume_input: global_config.total_volume_input,
+        total_volume_output: global_config.total_volume_output,
+        pda_authority: global_config.pda_authority,
+        admin_authority: global_config.admin_authority,
+        timestamp: ts,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SnapshotGlobalConfig<'info> {
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+}
@@ -0,0 +1,114 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, MakerOrderCount, Order, OrderBookSnapshot},
+    utils::consts::MAKER_ORDER_COUNT_SIZE,
+    LimoError, OrderDisplay,
+};
+
+pub fn handler_split_order(ctx: Context<SplitOrder>, split_input_amount: u64) -> Result<()> {
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let new_order = &mut ctx.accounts.new_order.load_init()?;
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(
+        global_config.max_orders_per_maker == 0
+            || ctx.accounts.maker_order_count.count < global_config.max_orders_per_maker,
+        LimoError::MakerOrderLimitReached
+    );
+
+    let order_before = **order;
+    operations::split_order(
+        order,
+        new_order,
+        global_config,
+        split_input_amount,
+        clock.unix_timestamp,
+        clock.slot,
+    )?;
+    let maker_order_count = &mut ctx.accounts.maker_order_count;
+    maker_order_count.count = maker_order_count.count.saturating_add(1);
+
+    let order_book_snapshot = &mut ctx.accounts.order_book_snapshot.load_mut()?;
+    operations::update_order_book_snapshot(order_book_snapshot, &order_before, order, clock.slot)?;
+    operations::add_order_to_order_book_snapshot(order_book_snapshot, new_order, clock.slot);
+
+    msg!(
+        "Split order {} into {}, input_amount {}, output_amount {}",
+        ctx.accounts.order.key(),
+        ctx.accounts.new_order.key(),
+        new_order.initial_input_amount,
+        new_order.expected_output_amount,
+    );
+
+    for (order_key, order) in [
+        (ctx.accounts.order.key(), &**order),
+        (ctx.accounts.new_order.key(), &**new_order),
+    ] {
+        let (vwap_numerator, vwap_denominator) =
+            operations::compute_vwap_fill_price(order).unwrap_or_default();
+
+        emit_cpi!(OrderDisplay {
+            initial_input_amount: order.initial_input_amount,
+            expected_output_amount: order.expected_output_amount,
+            remaining_input_amount: order.remaining_input_amount,
+            filled_output_amount: order.filled_output_amount,
+            tip_amount: order.tip_amount,
+            number_of_fills: order.number_of_fills,
+            on_event_output_amount_filled: 0,
+            on_event_tip_amount: 0,
+            order_type: order.order_type,
+            status: order.status,
+            last_updated_timestamp: order.last_updated_timestamp,
+            vwap_numerator,
+            vwap_denominator,
+            maker_note: order.maker_note_hex(),
+            fill_receipt_hash: [0; 32],
+            nonce: order.nonce,
+            tags: order.tags_hex(),
+            max_fills: order.max_fills,
+            maker: order.maker,
+            taker: Pubkey::default(),
+            input_mint: order.input_mint,
+            output_mint: order.output_mint,
+            order: order_key,
+        });
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SplitOrder<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(mut)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(zero)]
+    pub new_order: AccountLoader<'info, Order>,
+
+    #[account(mut, has_one = global_config)]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::MAKER_ORDER_COUNT_SEED, global_config.key().as_ref(), maker.key().as_ref()],
+        bump,
+        payer = maker,
+        space = MAKER_ORDER_COUNT_SIZE + 8
+    )]
+    pub maker_order_count: Box<Account<'info, MakerOrderCount>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,64 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, Order, StopLimitTrigger},
+    utils::{consts::STOP_LIMIT_TRIGGER_SIZE, oracle::get_pyth_price_checked},
+};
+
+pub fn handler_initialize_stop_limit_trigger(
+    ctx: Context<InitializeStopLimitTrigger>,
+    trigger_price_numerator: u64,
+    trigger_price_denominator: u64,
+) -> Result<()> {
+    get_pyth_price_checked(&ctx.accounts.price_feed, Clock::get()?.unix_timestamp)?;
+
+    let order = &ctx.accounts.order.load()?;
+    let stop_limit_trigger = &mut ctx.accounts.stop_limit_trigger;
+
+    operations::initialize_stop_limit_trigger(
+        order,
+        ctx.accounts.order.key(),
+        stop_limit_trigger,
+        ctx.accounts.price_feed.key(),
+        trigger_price_numerator,
+        trigger_price_denominator,
+    )?;
+
+    msg!(
+        "Set stop-limit trigger for order {} at {}/{} on feed {}",
+        ctx.accounts.order.key(),
+        trigger_price_numerator,
+        trigger_price_denominator,
+        ctx.accounts.price_feed.key(),
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeStopLimitTrigger<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        has_one = maker,
+        has_one = global_config
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    pub price_feed: AccountInfo<'info>,
+
+    #[account(
+        init,
+        seeds = [seeds::STOP_LIMIT_TRIGGER_SEED, order.key().as_ref()],
+        bump,
+        payer = maker,
+        space = STOP_LIMIT_TRIGGER_SIZE + 8
+    )]
+    pub stop_limit_trigger: Box<Account<'info, StopLimitTrigger>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,97 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    seeds,
+    state::{GlobalConfig, SwapProgramRegistry},
+    utils::consts::SWAP_PROGRAM_REGISTRY_SIZE,
+    LimoError, SwapProgramRegistered,
+};
+
+pub fn handler_register_swap_program(ctx: Context<RegisterSwapProgram>) -> Result<()> {
+    let swap_program = ctx.accounts.swap_program.key();
+    let registry = &mut ctx.accounts.swap_program_registry;
+    registry.global_config = ctx.accounts.global_config.key();
+
+    if !registry.is_registered(&swap_program) {
+        let slot = registry
+            .swap_programs
+            .iter_mut()
+            .find(|p| **p == Pubkey::default())
+            .ok_or_else(|| error!(LimoError::SwapProgramRegistryFull))?;
+        *slot = swap_program;
+    }
+
+    msg!(
+        "Registered swap program {} for global config {}",
+        swap_program,
+        ctx.accounts.global_config.key(),
+    );
+
+    emit_cpi!(SwapProgramRegistered {
+        global_config: ctx.accounts.global_config.key(),
+        swap_program,
+    });
+
+    Ok(())
+}
+
+pub fn handler_deregister_swap_program(ctx: Context<DeregisterSwapProgram>) -> Result<()> {
+    let swap_program = ctx.accounts.swap_program.key();
+    let registry = &mut ctx.accounts.swap_program_registry;
+
+    let slot = registry
+        .swap_programs
+        .iter_mut()
+        .find(|p| **p == swap_program)
+        .ok_or_else(|| error!(LimoError::SwapProgramNotRegistered))?;
+    *slot = Pubkey::default();
+
+    msg!(
+        "Deregistered swap program {} for global config {}",
+        swap_program,
+        ctx.accounts.global_config.key(),
+    );
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterSwapProgram<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(executable)]
+    pub swap_program: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [seeds::SWAP_PROGRAM_REGISTRY_SEED, global_config.key().as_ref()],
+        bump,
+        payer = admin_authority,
+        space = SWAP_PROGRAM_REGISTRY_SIZE + 8
+    )]
+    pub swap_program_registry: Box<Account<'info, SwapProgramRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterSwapProgram<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub swap_program: AccountInfo<'info>,
+
+    #[account(mut,
+        seeds = [seeds::SWAP_PROGRAM_REGISTRY_SEED, global_config.key().as_ref()],
+        bump,
+        has_one = global_config,
+    )]
+    pub swap_program_registry: Box<Account<'info, SwapProgramRegistry>>,
+}
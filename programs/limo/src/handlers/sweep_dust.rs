@@ -0,0 +1,86 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds, operations,
+    seeds::{self, GLOBAL_AUTH},
+    state::{GlobalConfig, VaultEscrow},
+    token_operations::transfer_from_vault_to_token_account,
+    LimoError,
+};
+
+pub fn handler_sweep_dust(ctx: Context<SweepDust>, recipient: Pubkey) -> Result<()> {
+    let gc = ctx.accounts.global_config.key();
+
+    let dust =
+        operations::compute_vault_dust(ctx.accounts.vault.amount, &ctx.accounts.vault_escrow);
+
+    if dust > 0 {
+        let pda_authority_bump = ctx.accounts.global_config.load()?.pda_authority_bump as u8;
+        let seeds: &[&[u8]] = global_seeds!(pda_authority_bump, &gc);
+
+        transfer_from_vault_to_token_account(
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            seeds,
+            dust,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    msg!(
+        "Swept {} dust from vault {} to {}, {} still owed to orders",
+        dust,
+        ctx.accounts.vault.key(),
+        recipient,
+        ctx.accounts.vault_escrow.outstanding_amount
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct SweepDust<'info> {
+    pub admin_authority: Signer<'info>,
+
+    #[account(
+        has_one = admin_authority,
+        has_one = pda_authority @ LimoError::InvalidPdaAuthority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = pda_authority,
+        token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub vault_escrow: Box<Account<'info, VaultEscrow>>,
+
+    #[account(mut,
+        token::mint = mint,
+        token::authority = recipient,
+        token::token_program = token_program,
+    )]
+    pub recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,37 +1,90 @@
 use anchor_lang::{prelude::*, Accounts};
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 use express_relay::{program::ExpressRelay, state::ExpressRelayMetadata};
 use solana_program::sysvar::{instructions::Instructions as SysInstructions, SysvarId};
 
 use crate::{
-    global_seeds, intermediary_seeds,
+    check_cpi_not_allowed, global_seeds, intermediary_seeds,
     operations::{self, validate_pda_authority_balance_and_update_accounting},
     seeds::{self, GLOBAL_AUTH, INTERMEDIARY_OUTPUT_TOKEN_ACCOUNT},
-    state::{GlobalConfig, Order, TakeOrderEffects},
+    state::{
+        DutchAuction, GlobalConfig, MakerAnalytics, MakerVolume, MintFeeConfig, Order,
+        OrderBookSnapshot, OrderCounterparties, OrderOutputRecipient, OrderReferrer,
+        PairOracleConfig, StopLimitTrigger, TakeOrderEffects, VaultEscrow,
+    },
     token_operations::{
-        close_ata_accounts_with_signer_seeds,
+        close_ata_accounts_with_signer_seeds, create_associated_token_account,
         initialize_intermediary_token_account_with_signer_seeds,
-        native_transfer_from_authority_to_user, native_transfer_from_user_to_account,
-        transfer_from_user_to_token_account, transfer_from_vault_to_token_account,
+        lamports_transfer_from_authority_to_account, native_transfer_from_authority_to_user,
+        native_transfer_from_user_to_account, transfer_from_user_to_token_account,
+        transfer_from_vault_to_token_account,
     },
-    utils::constraints::{
-        check_permission_express_relay_and_get_fees, is_counterparty_matching, is_wsol,
-        token_2022::validate_token_extensions, verify_ata,
+    utils::{
+        constraints::{
+            check_permission_express_relay_and_get_fees, get_order_output_owner,
+            get_token_account_checked, get_vwap_oracle_checked, is_counterparty_matching, is_wsol,
+            load_if_initialized,
+            token_2022::{transfer_fee_inclusive_amount, validate_token_extensions},
+            update_if_initialized, verify_ata,
+        },
+        oracle::{
+            check_fill_price_within_oracle_bounds, check_oracle_feed_for_pair,
+            get_optional_pyth_price_checked,
+        },
     },
     LimoError, OrderDisplay,
 };
 
-pub fn handler_take_order(
-    ctx: Context<TakeOrder>,
+pub fn handler_take_order_with_output_recipient<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TakeOrder<'info>>,
+    input_amount: u64,
+    min_output_amount: u64,
+    tip_amount_permissionless_taking: u64,
+    output_recipient: Pubkey,
+) -> Result<()> {
+    let order_output_recipient =
+        load_if_initialized::<OrderOutputRecipient>(&ctx.accounts.order_output_recipient)?
+            .ok_or(LimoError::InvalidOutputRecipient)?;
+    require_keys_eq!(
+        order_output_recipient.recipient,
+        output_recipient,
+        LimoError::InvalidOutputRecipient
+    );
+
+    handler_take_order(
+        ctx,
+        input_amount,
+        min_output_amount,
+        tip_amount_permissionless_taking,
+        false,
+    )
+}
+
+pub fn handler_take_order<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TakeOrder<'info>>,
     input_amount: u64,
     min_output_amount: u64,
     tip_amount_permissionless_taking: u64,
+    create_maker_ata_if_missing: bool,
 ) -> Result<()> {
-    validate_token_extensions(
+    check_cpi_not_allowed!(ctx);
+
+    let is_maker_output_ata_created =
+        create_maker_ata_if_missing && create_maker_output_ata_if_missing(&ctx)?;
+
+    let input_transfer_fee_bps = validate_token_extensions(
         &ctx.accounts.input_mint.to_account_info(),
         vec![&ctx.accounts.taker_input_ata.to_account_info()],
         false,
+        &[],
     )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
     if let Some(maker_output_ata_account) = ctx.accounts.maker_output_ata.as_ref() {
         validate_token_extensions(
             &ctx.accounts.output_mint.to_account_info(),
@@ -40,21 +93,35 @@ pub fn handler_take_order(
                 &maker_output_ata_account.to_account_info(),
             ],
             false,
+            &[],
         )?;
     } else {
         validate_token_extensions(
             &ctx.accounts.output_mint.to_account_info(),
             vec![&ctx.accounts.taker_output_ata.to_account_info()],
             false,
+            &[],
         )?;
     }
 
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
-    let is_filled_by_per = ctx.accounts.permission.is_some();
 
-    let (is_order_permissionless, counterparty) = {
+    let is_filled_by_per =
+        ctx.accounts.permission.is_some() && global_config.express_relay_disabled == 0;
+
+    let (
+        is_order_permissionless,
+        is_order_express_relay_only,
+        counterparty,
+        is_in_exclusive_taker_window,
+    ) = {
         let order = &ctx.accounts.order.load()?;
-        (order.permissionless != 0, order.counterparty)
+        (
+            order.permissionless != 0,
+            order.express_relay_only != 0,
+            order.counterparty,
+            order.is_in_exclusive_taker_window(Clock::get()?.unix_timestamp),
+        )
     };
 
     let tip = check_permission_and_get_tip(
@@ -62,23 +129,107 @@ pub fn handler_take_order(
         &counterparty,
         tip_amount_permissionless_taking,
         is_order_permissionless,
+        is_order_express_relay_only,
+        is_in_exclusive_taker_window,
         is_filled_by_per,
     )?;
+    let tip = if is_maker_output_ata_created && !is_filled_by_per {
+        tip.checked_sub(global_config.ata_creation_cost)
+            .ok_or(LimoError::TipBelowAtaCreationCost)?
+    } else {
+        tip
+    };
+    // The minimum applies to what is left for the maker once the ATA rent is credited
+    operations::check_min_tip(global_config, tip, is_filled_by_per)?;
+
+    let vwap_oracle = get_vwap_oracle_checked(
+        ctx.remaining_accounts,
+        &ctx.accounts.global_config.key(),
+        &ctx.accounts.input_mint.key(),
+        &ctx.accounts.output_mint.key(),
+    )?;
+    let mut vwap_oracle_state = vwap_oracle.as_ref().map(|o| o.load_mut()).transpose()?;
+    let mint_fee_config_state = ctx
+        .accounts
+        .mint_fee_config
+        .as_ref()
+        .map(|c| c.load())
+        .transpose()?;
+    let mut maker_volume_state = ctx
+        .accounts
+        .maker_volume
+        .as_ref()
+        .map(|v| v.load_mut())
+        .transpose()?;
+    let mut maker_analytics_state = ctx
+        .accounts
+        .maker_analytics
+        .as_ref()
+        .map(|a| a.load_mut())
+        .transpose()?;
 
     let order = &mut ctx.accounts.order.load_mut()?;
     let clock = Clock::get()?;
 
+    let oracle_price = get_optional_pyth_price_checked(
+        ctx.accounts.pyth_price_account.as_ref(),
+        ctx.accounts
+            .stop_limit_trigger
+            .as_ref()
+            .map(|trigger| &trigger.price_feed),
+        clock.unix_timestamp,
+    )?;
+    check_oracle_feed_for_pair(
+        oracle_price.as_ref(),
+        ctx.accounts.pair_oracle_config.as_deref(),
+        global_config.oracle_price_tolerance_bps,
+    )?;
+
+    let order_before = **order;
     let TakeOrderEffects {
         input_to_send_to_taker,
         output_to_send_to_maker,
+        boost_to_send_to_taker,
+        referrer_tip_to_send,
     } = operations::take_order(
         global_config,
         order,
         input_amount,
         tip,
         clock.unix_timestamp,
+        clock.slot,
         min_output_amount,
+        vwap_oracle_state.as_deref_mut(),
+        mint_fee_config_state.as_deref(),
+        load_if_initialized::<OrderReferrer>(&ctx.accounts.order_referrer)?.is_some(),
+        ctx.accounts.dutch_auction.as_deref(),
+        ctx.accounts
+            .stop_limit_trigger
+            .as_deref()
+            .zip(oracle_price.as_ref()),
+        maker_volume_state.as_deref_mut(),
+        maker_analytics_state.as_deref_mut(),
     )?;
+    operations::update_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        &order_before,
+        order,
+        clock.slot,
+    )?;
+
+    if let Some(oracle_price) = oracle_price
+        .as_ref()
+        .filter(|_| global_config.oracle_price_tolerance_bps > 0)
+    {
+        check_fill_price_within_oracle_bounds(
+            oracle_price,
+            input_to_send_to_taker,
+            ctx.accounts.input_mint.decimals,
+            output_to_send_to_maker,
+            ctx.accounts.output_mint.decimals,
+            global_config.oracle_price_tolerance_bps,
+        )?;
+    }
 
     transfer_output_to_maker_and_input_to_taker(
         &ctx,
@@ -87,7 +238,24 @@ pub fn handler_take_order(
         output_to_send_to_maker,
     )?;
 
-    tip_transfer_and_validation(&ctx, global_config, tip, is_filled_by_per)?;
+    tip_transfer_and_validation(
+        &ctx,
+        global_config,
+        tip,
+        boost_to_send_to_taker,
+        referrer_tip_to_send,
+        is_filled_by_per,
+    )?;
+
+    let (vwap_numerator, vwap_denominator) =
+        operations::compute_vwap_fill_price(order).unwrap_or_default();
+    let fill_receipt_hash = operations::compute_fill_receipt_hash(
+        &ctx.accounts.order.key(),
+        order.number_of_fills,
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        order.last_updated_timestamp,
+    );
 
     emit_cpi!(OrderDisplay {
         initial_input_amount: order.initial_input_amount,
@@ -101,6 +269,18 @@ pub fn handler_take_order(
         order_type: order.order_type,
         status: order.status,
         last_updated_timestamp: order.last_updated_timestamp,
+        vwap_numerator,
+        vwap_denominator,
+        maker_note: order.maker_note_hex(),
+        fill_receipt_hash,
+        nonce: order.nonce,
+        tags: order.tags_hex(),
+        max_fills: order.max_fills,
+        maker: order.maker,
+        taker: ctx.accounts.taker.key(),
+        input_mint: order.input_mint,
+        output_mint: order.output_mint,
+        order: ctx.accounts.order.key(),
     });
 
     Ok(())
@@ -150,6 +330,13 @@ pub struct TakeOrder<'info> {
     )]
     pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
     #[account(mut,
         token::mint = input_mint,
         token::authority = taker
@@ -168,11 +355,8 @@ pub struct TakeOrder<'info> {
     )]
     pub intermediary_output_token_account: Option<UncheckedAccount<'info>>,
 
-    #[account(mut,
-        token::mint = output_mint,
-        token::authority = maker,
-    )]
-    pub maker_output_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub maker_output_ata: Option<UncheckedAccount<'info>>,
 
     #[account(address = express_relay::ID)]
     pub express_relay: Program<'info, ExpressRelay>,
@@ -194,6 +378,141 @@ pub struct TakeOrder<'info> {
     pub rent: Sysvar<'info, Rent>,
 
     pub system_program: Program<'info, System>,
+
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+
+    #[account(
+        seeds = [
+            seeds::MINT_FEE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub mint_fee_config: Option<AccountLoader<'info, MintFeeConfig>>,
+
+    /// CHECK: address is verified, deserialized only when initialized
+    #[account(
+        seeds = [seeds::REFERRER_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_referrer: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub referrer: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [seeds::DUTCH_AUCTION_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub dutch_auction: Option<Account<'info, DutchAuction>>,
+
+    pub pyth_price_account: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [
+            seeds::PAIR_ORACLE_CONFIG_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub pair_oracle_config: Option<Account<'info, PairOracleConfig>>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    #[account(mut)]
+    pub maker_volume: Option<AccountLoader<'info, MakerVolume>>,
+
+    #[account(
+        seeds = [seeds::ORDER_COUNTERPARTIES_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub order_counterparties: Option<Box<Account<'info, OrderCounterparties>>>,
+
+    #[account(
+        seeds = [seeds::STOP_LIMIT_TRIGGER_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub stop_limit_trigger: Option<Account<'info, StopLimitTrigger>>,
+
+    #[account(mut)]
+    pub maker_analytics: Option<AccountLoader<'info, MakerAnalytics>>,
+
+    /// CHECK: address is verified, deserialized only when initialized
+    #[account(
+        seeds = [seeds::ORDER_OUTPUT_RECIPIENT_SEED, order.key().as_ref()],
+        bump
+    )]
+    pub order_output_recipient: UncheckedAccount<'info>,
+}
+
+/// Returns whether the ata was created, in which case the taker has paid its
+/// rent and is credited `ata_creation_cost` against the tip. Orders paying a
+/// separate output recipient never get a maker ata created.
+fn create_maker_output_ata_if_missing(ctx: &Context<TakeOrder>) -> Result<bool> {
+    if is_wsol(&ctx.accounts.output_mint.key())
+        || get_order_output_owner(
+            &ctx.accounts.order_output_recipient,
+            &ctx.accounts.maker.key(),
+        )? != ctx.accounts.maker.key()
+    {
+        return Ok(false);
+    }
+
+    let maker_output_ata_account = ctx
+        .accounts
+        .maker_output_ata
+        .as_ref()
+        .ok_or(LimoError::MakerOutputAtaRequired)?;
+    if maker_output_ata_account.data_len() > 0 {
+        return Ok(false);
+    }
+
+    verify_ata(
+        &ctx.accounts.maker.key(),
+        &ctx.accounts.output_mint.key(),
+        &maker_output_ata_account.key(),
+        &ctx.accounts.output_token_program.key(),
+    )?;
+
+    let associated_token_program = ctx
+        .accounts
+        .associated_token_program
+        .as_ref()
+        .ok_or(LimoError::MakerOutputAtaCreationFailed)?;
+
+    create_associated_token_account(
+        ctx.accounts.taker.to_account_info(),
+        maker_output_ata_account.to_account_info(),
+        ctx.accounts.maker.to_account_info(),
+        ctx.accounts.output_mint.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.output_token_program.to_account_info(),
+        associated_token_program.to_account_info(),
+    )
+    .map_err(|_| error!(LimoError::MakerOutputAtaCreationFailed))?;
+
+    msg!(
+        "Created maker output ata {} paid by taker {}",
+        maker_output_ata_account.key(),
+        ctx.accounts.taker.key()
+    );
+
+    Ok(true)
 }
 
 fn check_permission_and_get_tip(
@@ -201,16 +520,35 @@ fn check_permission_and_get_tip(
     order_counterparty: &Pubkey,
     tip_amount_permissionless_taking: u64,
     is_order_permissionless: bool,
+    is_order_express_relay_only: bool,
+    is_in_exclusive_taker_window: bool,
     is_filled_by_per: bool,
 ) -> Result<u64> {
     if !is_order_permissionless && !is_filled_by_per {
         return err!(LimoError::PermissionRequiredPermissionlessNotEnabled);
     }
 
-    if !is_counterparty_matching(order_counterparty, &ctx.accounts.taker.key()) {
+    if is_order_express_relay_only && !is_filled_by_per {
+        return err!(LimoError::PermissionRequiredPermissionlessNotEnabled);
+    }
+
+    let additional_counterparties = ctx
+        .accounts
+        .order_counterparties
+        .as_ref()
+        .map_or(&[][..], |c| &c.additional_counterparties[..]);
+    if !is_counterparty_matching(
+        order_counterparty,
+        additional_counterparties,
+        &ctx.accounts.taker.key(),
+    ) {
         return err!(LimoError::CounterpartyDisallowed);
     }
 
+    if is_in_exclusive_taker_window && ctx.accounts.taker.key() != *order_counterparty {
+        return err!(LimoError::ExclusiveTakerWindowActive);
+    }
+
     let tip = if !is_filled_by_per {
         tip_amount_permissionless_taking
     } else {
@@ -238,6 +576,15 @@ fn transfer_output_to_maker_and_input_to_taker(
     let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
 
     let output_is_wsol = is_wsol(&ctx.accounts.output_mint.key());
+    let output_owner = get_order_output_owner(
+        &ctx.accounts.order_output_recipient,
+        &ctx.accounts.maker.key(),
+    )?;
+    require!(
+        !output_is_wsol || output_owner == ctx.accounts.maker.key(),
+        LimoError::InvalidOutputRecipient
+    );
+
     let output_destination_token_account = if output_is_wsol {
         let intermediary_output_token_account = ctx
             .accounts
@@ -265,21 +612,31 @@ fn transfer_output_to_maker_and_input_to_taker(
             .as_ref()
             .ok_or(LimoError::MakerOutputAtaRequired)?;
         verify_ata(
-            &ctx.accounts.maker.key(),
+            &output_owner,
             &ctx.accounts.output_mint.key(),
             &maker_output_ata_account.key(),
             &ctx.accounts.output_token_program.key(),
         )?;
+        get_token_account_checked(
+            &maker_output_ata_account.to_account_info(),
+            &ctx.accounts.output_mint.key(),
+            &output_owner,
+        )?;
         maker_output_ata_account.to_account_info()
     };
 
+    let gross_output_amount = transfer_fee_inclusive_amount(
+        &ctx.accounts.output_mint.to_account_info(),
+        output_to_send_to_maker,
+        Clock::get()?.epoch,
+    )?;
     transfer_from_user_to_token_account(
         ctx.accounts.taker_output_ata.to_account_info(),
         output_destination_token_account.clone(),
         ctx.accounts.taker.to_account_info(),
         ctx.accounts.output_mint.to_account_info(),
         ctx.accounts.output_token_program.to_account_info(),
-        output_to_send_to_maker,
+        gross_output_amount,
         ctx.accounts.output_mint.decimals,
     )?;
 
@@ -310,6 +667,13 @@ fn transfer_output_to_maker_and_input_to_taker(
         ctx.accounts.input_mint.decimals,
     )?;
 
+    update_if_initialized(
+        &ctx.accounts.vault_escrow,
+        |vault_escrow: &mut VaultEscrow| {
+            operations::release_vault_escrow(vault_escrow, input_to_send_to_taker)
+        },
+    )?;
+
     Ok(())
 }
 
@@ -317,6 +681,8 @@ fn tip_transfer_and_validation(
     ctx: &Context<TakeOrder>,
     global_config: &mut GlobalConfig,
     tip: u64,
+    boost: u64,
+    referrer_tip: u64,
     is_filled_by_per: bool,
 ) -> Result<()> {
     if !is_filled_by_per {
@@ -333,5 +699,46 @@ fn tip_transfer_and_validation(
         pda_authority_balance,
         tip,
     )?;
+
+    if referrer_tip > 0 {
+        let order_referrer = load_if_initialized::<OrderReferrer>(&ctx.accounts.order_referrer)?
+            .ok_or(LimoError::InvalidAccount)?;
+        let referrer = ctx
+            .accounts
+            .referrer
+            .as_ref()
+            .ok_or(LimoError::InvalidAccount)?;
+        require_keys_eq!(
+            referrer.key(),
+            order_referrer.referrer,
+            LimoError::InvalidAccount
+        );
+
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            referrer.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            referrer_tip,
+        )?;
+        global_config.pda_authority_previous_lamports_balance =
+            ctx.accounts.pda_authority.lamports();
+    }
+
+    if boost > 0 {
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.taker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            boost,
+        )?;
+        global_config.pda_authority_previous_lamports_balance =
+            ctx.accounts.pda_authority.lamports();
+    }
     Ok(())
 }
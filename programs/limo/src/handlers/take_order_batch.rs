@@ -0,0 +1,354 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    global_seeds,
+    operations::{self, validate_pda_authority_balance_and_update_accounting},
+    seeds::{self, GLOBAL_AUTH},
+    state::{
+        GlobalConfig, Order, OrderBookSnapshot, OrderReferrer, TakeOrderBatchEntry,
+        TakeOrderEffects, VaultEscrow,
+    },
+    token_operations::{
+        lamports_transfer_from_authority_to_account, native_transfer_from_user_to_account,
+        transfer_from_user_to_token_account, transfer_from_vault_to_token_account,
+    },
+    utils::{
+        constraints::{
+            get_order_output_owner, get_token_account_checked, is_counterparty_matching, is_wsol,
+            load_if_initialized,
+            token_2022::{transfer_fee_inclusive_amount, validate_token_extensions},
+            update_if_initialized, verify_ata,
+        },
+        consts::MAX_TAKE_ORDER_BATCH_SIZE,
+    },
+    LimoError, OrderDisplay,
+};
+
+pub fn handler_take_order_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TakeOrderBatch<'info>>,
+    entries: Vec<TakeOrderBatchEntry>,
+) -> Result<()> {
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_TAKE_ORDER_BATCH_SIZE,
+        LimoError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * 4,
+        LimoError::InvalidBatchSize
+    );
+    require!(
+        !is_wsol(&ctx.accounts.output_mint.key()),
+        LimoError::IntermediaryOutputTokenAccountRequired
+    );
+
+    let input_transfer_fee_bps = validate_token_extensions(
+        &ctx.accounts.input_mint.to_account_info(),
+        vec![&ctx.accounts.taker_input_ata.to_account_info()],
+        false,
+        &[],
+    )?;
+    require!(
+        input_transfer_fee_bps == 0,
+        LimoError::UnsupportedTokenExtension
+    );
+    validate_token_extensions(
+        &ctx.accounts.output_mint.to_account_info(),
+        vec![&ctx.accounts.taker_output_ata.to_account_info()],
+        false,
+        &[],
+    )?;
+
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    let gc = ctx.accounts.global_config.key();
+    let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+    let taker = ctx.accounts.taker.key();
+    let clock = Clock::get()?;
+    let order_book_snapshot = &mut ctx.accounts.order_book_snapshot.load_mut()?;
+
+    let mut total_tip: u64 = 0;
+    let mut total_boost: u64 = 0;
+    for (entry, accounts) in entries.iter().zip(ctx.remaining_accounts.chunks(4)) {
+        let (order_account, maker_output_ata, order_referrer, order_output_recipient) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        require_keys_eq!(order_account.key(), entry.order, LimoError::InvalidAccount);
+        require_keys_eq!(
+            order_referrer.key(),
+            Pubkey::find_program_address(
+                &[seeds::REFERRER_SEED, order_account.key().as_ref()],
+                &crate::ID
+            )
+            .0,
+            LimoError::InvalidAccount
+        );
+        require!(
+            load_if_initialized::<OrderReferrer>(order_referrer)?.is_none(),
+            LimoError::ReferredOrderNotSupportedInBatch
+        );
+        require_keys_eq!(
+            order_output_recipient.key(),
+            Pubkey::find_program_address(
+                &[
+                    seeds::ORDER_OUTPUT_RECIPIENT_SEED,
+                    order_account.key().as_ref()
+                ],
+                &crate::ID
+            )
+            .0,
+            LimoError::InvalidAccount
+        );
+
+        let order_loader = AccountLoader::<Order>::try_from(order_account)?;
+        let order = &mut order_loader.load_mut()?;
+
+        require_keys_eq!(order.global_config, gc, LimoError::InvalidOrderGlobalConfig);
+        require_keys_eq!(
+            order.input_mint,
+            ctx.accounts.input_mint.key(),
+            LimoError::InvalidTokenMint
+        );
+        require_keys_eq!(
+            order.output_mint,
+            ctx.accounts.output_mint.key(),
+            LimoError::InvalidTokenMint
+        );
+        require!(
+            order.permissionless != 0 && order.express_relay_only == 0,
+            LimoError::PermissionRequiredPermissionlessNotEnabled
+        );
+        require!(
+            is_counterparty_matching(&order.counterparty, &[], &taker),
+            LimoError::CounterpartyDisallowed
+        );
+        require!(
+            !order.is_in_exclusive_taker_window(clock.unix_timestamp)
+                || order.counterparty == taker,
+            LimoError::ExclusiveTakerWindowActive
+        );
+        require!(
+            entry.tip_amount >= global_config.min_tip_amount,
+            LimoError::TipBelowMinimum
+        );
+
+        let output_owner = get_order_output_owner(order_output_recipient, &order.maker)?;
+        verify_ata(
+            &output_owner,
+            &ctx.accounts.output_mint.key(),
+            &maker_output_ata.key(),
+            &ctx.accounts.output_token_program.key(),
+        )?;
+        get_token_account_checked(
+            maker_output_ata,
+            &ctx.accounts.output_mint.key(),
+            &output_owner,
+        )?;
+
+        let order_before = **order;
+        let TakeOrderEffects {
+            input_to_send_to_taker,
+            output_to_send_to_maker,
+            boost_to_send_to_taker,
+            ..
+        } = operations::take_order(
+            global_config,
+            order,
+            entry.input_amount,
+            entry.tip_amount,
+            clock.unix_timestamp,
+            clock.slot,
+            entry.min_output_amount,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        operations::update_order_book_snapshot(
+            order_book_snapshot,
+            &order_before,
+            order,
+            clock.slot,
+        )?;
+
+        let gross_output_amount = transfer_fee_inclusive_amount(
+            &ctx.accounts.output_mint.to_account_info(),
+            output_to_send_to_maker,
+            Clock::get()?.epoch,
+        )?;
+        transfer_from_user_to_token_account(
+            ctx.accounts.taker_output_ata.to_account_info(),
+            maker_output_ata.clone(),
+            ctx.accounts.taker.to_account_info(),
+            ctx.accounts.output_mint.to_account_info(),
+            ctx.accounts.output_token_program.to_account_info(),
+            gross_output_amount,
+            ctx.accounts.output_mint.decimals,
+        )?;
+
+        transfer_from_vault_to_token_account(
+            ctx.accounts.taker_input_ata.to_account_info(),
+            ctx.accounts.input_vault.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.input_mint.to_account_info(),
+            ctx.accounts.input_token_program.to_account_info(),
+            seeds,
+            input_to_send_to_taker,
+            ctx.accounts.input_mint.decimals,
+        )?;
+
+        update_if_initialized(
+            &ctx.accounts.vault_escrow,
+            |vault_escrow: &mut VaultEscrow| {
+                operations::release_vault_escrow(vault_escrow, input_to_send_to_taker)
+            },
+        )?;
+
+        total_tip = total_tip
+            .checked_add(entry.tip_amount)
+            .ok_or(LimoError::MathOverflow)?;
+        total_boost = total_boost
+            .checked_add(boost_to_send_to_taker)
+            .ok_or(LimoError::MathOverflow)?;
+
+        let (vwap_numerator, vwap_denominator) =
+            operations::compute_vwap_fill_price(order).unwrap_or_default();
+        let fill_receipt_hash = operations::compute_fill_receipt_hash(
+            &order_account.key(),
+            order.number_of_fills,
+            input_to_send_to_taker,
+            output_to_send_to_maker,
+            order.last_updated_timestamp,
+        );
+
+        emit_cpi!(OrderDisplay {
+            initial_input_amount: order.initial_input_amount,
+            expected_output_amount: order.expected_output_amount,
+            remaining_input_amount: order.remaining_input_amount,
+            filled_output_amount: order.filled_output_amount,
+            tip_amount: order.tip_amount,
+            number_of_fills: order.number_of_fills,
+            on_event_output_amount_filled: output_to_send_to_maker,
+            on_event_tip_amount: entry.tip_amount,
+            order_type: order.order_type,
+            status: order.status,
+            last_updated_timestamp: order.last_updated_timestamp,
+            vwap_numerator,
+            vwap_denominator,
+            maker_note: order.maker_note_hex(),
+            fill_receipt_hash,
+            nonce: order.nonce,
+            tags: order.tags_hex(),
+            max_fills: order.max_fills,
+            maker: order.maker,
+            taker,
+            input_mint: order.input_mint,
+            output_mint: order.output_mint,
+            order: order_account.key(),
+        });
+    }
+
+    native_transfer_from_user_to_account(
+        ctx.accounts.taker.to_account_info(),
+        ctx.accounts.pda_authority.to_account_info(),
+        total_tip,
+    )?;
+
+    let pda_authority_balance = ctx.accounts.pda_authority.lamports();
+    validate_pda_authority_balance_and_update_accounting(
+        global_config,
+        pda_authority_balance,
+        total_tip,
+    )?;
+
+    if total_boost > 0 {
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.taker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            total_boost,
+        )?;
+        global_config.pda_authority_previous_lamports_balance =
+            ctx.accounts.pda_authority.lamports();
+    }
+
+    msg!(
+        "Batch filled {} orders, total tip {}, total boost {}",
+        entries.len(),
+        total_tip,
+        total_boost
+    );
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TakeOrderBatch<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    #[account(
+        mint::token_program = input_token_program,
+    )]
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mint::token_program = output_token_program,
+    )]
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut,
+        seeds = [seeds::ESCROW_VAULT, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump,
+        token::mint = input_mint,
+        token::authority = pda_authority
+    )]
+    pub input_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: address is verified, tracks the vault's outstanding escrow once initialized
+    #[account(mut,
+        seeds = [seeds::VAULT_ESCROW_SEED, global_config.key().as_ref(), input_mint.key().as_ref()],
+        bump
+    )]
+    pub vault_escrow: UncheckedAccount<'info>,
+
+    #[account(mut,
+        seeds = [
+            seeds::ORDER_BOOK_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    #[account(mut,
+        token::mint = input_mint,
+        token::authority = taker
+    )]
+    pub taker_input_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut,
+        token::mint = output_mint,
+        token::authority = taker
+    )]
+    pub taker_output_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub input_token_program: Interface<'info, TokenInterface>,
+    pub output_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,9 +1,13 @@
 use anchor_lang::{prelude::*, Accounts};
 
 use crate::{
-    operations,
-    state::{GlobalConfig, UpdateGlobalConfigMode},
-    utils::consts::UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
+    operations, seeds,
+    state::{GlobalConfig, GlobalConfigUpdateEntry, MultiSigAdmin, UpdateGlobalConfigMode},
+    utils::consts::{
+        MAX_UPDATE_GLOBAL_CONFIG_BATCH_SIZE, UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE,
+        UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
+    },
+    DeprecatedFieldWriteAttempted, LimoError, ProtocolVersionUpdated,
 };
 
 pub fn handler_update_global_config(
@@ -13,15 +17,94 @@ pub fn handler_update_global_config(
 ) -> Result<()> {
     let ts = Clock::get()?.unix_timestamp;
     let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    require!(
+        global_config.multisig_enabled == 0,
+        LimoError::MultisigEnabled
+    );
 
     let mode =
         UpdateGlobalConfigMode::try_from(mode).map_err(|_| ProgramError::InvalidInstructionData)?;
 
+    if mode == UpdateGlobalConfigMode::UpdateMultisigEnabled && value[0] != 0 {
+        operations::check_multisig_admin_can_take_over(ctx.accounts.multisig_admin.as_deref())?;
+    }
+
+    let prev_protocol_version = global_config.protocol_version;
     operations::update_global_config(global_config, mode, value, ts.try_into().unwrap())?;
 
+    if mode == UpdateGlobalConfigMode::UpdateOrderTakingPermissionless {
+        emit_cpi!(DeprecatedFieldWriteAttempted {
+            mode: mode as u16,
+            ts: ts.try_into().unwrap(),
+        });
+    }
+
+    if mode == UpdateGlobalConfigMode::UpdateProtocolVersion {
+        emit_cpi!(ProtocolVersionUpdated {
+            old: prev_protocol_version,
+            new: global_config.protocol_version,
+            ts: ts.try_into().unwrap(),
+        });
+    }
+
     Ok(())
 }
 
+pub fn handler_update_global_config_v2(
+    ctx: Context<UpdateGlobalConfig>,
+    updates: Vec<GlobalConfigUpdateEntry>,
+) -> Result<()> {
+    require!(
+        !updates.is_empty() && updates.len() <= MAX_UPDATE_GLOBAL_CONFIG_BATCH_SIZE,
+        LimoError::InvalidBatchSize
+    );
+
+    let ts = Clock::get()?.unix_timestamp;
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+    require!(
+        global_config.multisig_enabled == 0,
+        LimoError::MultisigEnabled
+    );
+
+    for (idx, update) in updates.iter().enumerate() {
+        let mode = UpdateGlobalConfigMode::try_from(update.mode).map_err(|_| {
+            msg!("update_global_config_v2 invalid mode at index {}", idx);
+            ProgramError::InvalidInstructionData
+        })?;
+
+        let mut value = [0; UPDATE_GLOBAL_CONFIG_BYTE_SIZE];
+        value[..UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE].copy_from_slice(&update.value);
+
+        if mode == UpdateGlobalConfigMode::UpdateMultisigEnabled && value[0] != 0 {
+            operations::check_multisig_admin_can_take_over(ctx.accounts.multisig_admin.as_deref())?;
+        }
+
+        let prev_protocol_version = global_config.protocol_version;
+        operations::update_global_config(global_config, mode, &value, ts.try_into().unwrap())
+            .inspect_err(|_| {
+                msg!("update_global_config_v2 failed at index {}", idx);
+            })?;
+
+        if mode == UpdateGlobalConfigMode::UpdateOrderTakingPermissionless {
+            emit_cpi!(DeprecatedFieldWriteAttempted {
+                mode: mode as u16,
+                ts: ts.try_into().unwrap(),
+            });
+        }
+
+        if mode == UpdateGlobalConfigMode::UpdateProtocolVersion {
+            emit_cpi!(ProtocolVersionUpdated {
+                old: prev_protocol_version,
+                new: global_config.protocol_version,
+                ts: ts.try_into().unwrap(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateGlobalConfig<'info> {
     #[account(mut)]
@@ -30,4 +113,11 @@ pub struct UpdateGlobalConfig<'info> {
     #[account(mut,
         has_one = admin_authority,)]
     pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [seeds::MULTISIG_ADMIN_SEED, global_config.key().as_ref()],
+        bump = multisig_admin.bump,
+        has_one = global_config
+    )]
+    pub multisig_admin: Option<Account<'info, MultiSigAdmin>>,
 }
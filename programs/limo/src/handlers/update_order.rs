@@ -1,22 +1,83 @@
 use anchor_lang::prelude::*;
 
-use crate::{operations, state::Order, GlobalConfig, UpdateOrderMode};
+use crate::{
+    operations, seeds,
+    state::{Order, OrderBookSnapshot, OrderCounterparties, OrderDelegate},
+    utils::constraints::signer_is_maker_or_delegate,
+    GlobalConfig, OrderDisplay, UpdateOrderMode,
+};
 
 pub fn handler_update_order(ctx: Context<UpdateOrder>, mode: u16, value: &[u8]) -> Result<()> {
+    signer_is_maker_or_delegate(
+        &ctx.accounts.maker,
+        ctx.accounts.delegate.as_ref(),
+        ctx.accounts.order_delegate.as_deref().map(|d| &**d),
+    )?;
+
     let order = &mut ctx.accounts.order.load_mut()?;
+    let global_config = &ctx.accounts.global_config.load()?;
+    let slot = Clock::get()?.slot;
 
     let mode = UpdateOrderMode::try_from(mode).map_err(|_| ProgramError::InvalidInstructionData)?;
 
-    operations::update_order(order, mode, value)?;
+    let order_before = **order;
+    operations::update_order(
+        order,
+        global_config,
+        ctx.accounts
+            .order_counterparties
+            .as_deref_mut()
+            .map(|c| &mut **c),
+        mode,
+        value,
+        slot,
+    )?;
+    operations::update_order_book_snapshot(
+        &mut *ctx.accounts.order_book_snapshot.load_mut()?,
+        &order_before,
+        order,
+        slot,
+    )?;
 
     msg!("Updating order with mode {:?} and value {:?}", mode, &value);
 
+    if mode == UpdateOrderMode::UpdateExpectedOutputAmount {
+        let (vwap_numerator, vwap_denominator) =
+            operations::compute_vwap_fill_price(order).unwrap_or_default();
+
+        emit!(OrderDisplay {
+            initial_input_amount: order.initial_input_amount,
+            expected_output_amount: order.expected_output_amount,
+            remaining_input_amount: order.remaining_input_amount,
+            filled_output_amount: order.filled_output_amount,
+            tip_amount: order.tip_amount,
+            number_of_fills: order.number_of_fills,
+            on_event_output_amount_filled: 0,
+            on_event_tip_amount: 0,
+            order_type: order.order_type,
+            status: order.status,
+            last_updated_timestamp: order.last_updated_timestamp,
+            vwap_numerator,
+            vwap_denominator,
+            maker_note: order.maker_note_hex(),
+            fill_receipt_hash: [0; 32],
+            nonce: order.nonce,
+            tags: order.tags_hex(),
+            max_fills: order.max_fills,
+            maker: order.maker,
+            taker: Pubkey::default(),
+            input_mint: order.input_mint,
+            output_mint: order.output_mint,
+            order: ctx.accounts.order.key(),
+        });
+    }
+
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct UpdateOrder<'info> {
-    pub maker: Signer<'info>,
+    pub maker: AccountInfo<'info>,
 
     pub global_config: AccountLoader<'info, GlobalConfig>,
 
@@ -24,4 +85,23 @@ pub struct UpdateOrder<'info> {
         has_one = maker,
         has_one = global_config)]
     pub order: AccountLoader<'info, Order>,
+
+    #[account(mut, has_one = global_config)]
+    pub order_book_snapshot: AccountLoader<'info, OrderBookSnapshot>,
+
+    pub delegate: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [seeds::ORDER_DELEGATE_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub order_delegate: Option<Box<Account<'info, OrderDelegate>>>,
+
+    #[account(mut,
+        seeds = [seeds::ORDER_COUNTERPARTIES_SEED, order.key().as_ref()],
+        bump,
+        has_one = order
+    )]
+    pub order_counterparties: Option<Box<Account<'info, OrderCounterparties>>>,
 }
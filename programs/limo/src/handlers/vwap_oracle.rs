@@ -0,0 +1,88 @@
+use anchor_lang::{prelude::*, Accounts};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    operations, seeds,
+    state::{GlobalConfig, VwapOracle},
+    utils::consts::VWAP_ORACLE_SIZE,
+    VwapOracleDisplay,
+};
+
+pub fn handler_initialize_vwap_oracle(
+    ctx: Context<InitializeVwapOracle>,
+    window_seconds: u64,
+) -> Result<()> {
+    let vwap_oracle = &mut ctx.accounts.vwap_oracle.load_init()?;
+    let ts = u64::try_from(Clock::get()?.unix_timestamp).unwrap();
+
+    operations::initialize_vwap_oracle(
+        vwap_oracle,
+        ctx.accounts.global_config.key(),
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        window_seconds,
+        ts,
+    )?;
+
+    msg!(
+        "Initializing vwap oracle for global config {} with input mint {}, output mint {}, window {}s",
+        ctx.accounts.global_config.key(),
+        ctx.accounts.input_mint.key(),
+        ctx.accounts.output_mint.key(),
+        window_seconds,
+    );
+
+    Ok(())
+}
+
+pub fn handler_read_vwap_oracle(ctx: Context<ReadVwapOracle>) -> Result<()> {
+    let vwap_oracle = ctx.accounts.vwap_oracle.load()?;
+
+    emit_cpi!(VwapOracleDisplay {
+        input_mint: vwap_oracle.input_mint,
+        output_mint: vwap_oracle.output_mint,
+        cumulative_input_volume: vwap_oracle.cumulative_input_volume,
+        cumulative_output_volume: vwap_oracle.cumulative_output_volume,
+        last_update_timestamp: vwap_oracle.last_update_timestamp,
+        window_seconds: vwap_oracle.window_seconds,
+        vwap_numerator: vwap_oracle.vwap_numerator,
+        vwap_denominator: vwap_oracle.vwap_denominator,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeVwapOracle<'info> {
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+
+    #[account(has_one = admin_authority)]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    pub input_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub output_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [
+            seeds::VWAP_ORACLE_SEED,
+            global_config.key().as_ref(),
+            input_mint.key().as_ref(),
+            output_mint.key().as_ref()
+        ],
+        bump,
+        payer = admin_authority,
+        space = VWAP_ORACLE_SIZE + 8
+    )]
+    pub vwap_oracle: AccountLoader<'info, VwapOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReadVwapOracle<'info> {
+    pub vwap_oracle: AccountLoader<'info, VwapOracle>,
+}
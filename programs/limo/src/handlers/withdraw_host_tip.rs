@@ -17,7 +17,7 @@ pub fn withdraw_host_tip(ctx: Context<WithdrawHostTip>) -> Result<()> {
 
     if host_tip_to_withdraw > 0 {
         lamports_transfer_from_authority_to_account(
-            ctx.accounts.admin_authority.to_account_info(),
+            ctx.accounts.fee_collector.to_account_info(),
             ctx.accounts.pda_authority.to_account_info(),
             ctx.accounts.system_program.to_account_info(),
             seeds,
@@ -32,7 +32,6 @@ pub fn withdraw_host_tip(ctx: Context<WithdrawHostTip>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct WithdrawHostTip<'info> {
-    #[account(mut)]
     pub admin_authority: Signer<'info>,
 
     #[account(
@@ -45,5 +44,8 @@ pub struct WithdrawHostTip<'info> {
     #[account(mut)]
     pub pda_authority: AccountInfo<'info>,
 
+    #[account(mut, address = global_config.load()?.effective_fee_collector())]
+    pub fee_collector: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::*, Accounts};
+
+use crate::{
+    global_seeds, operations,
+    seeds::GLOBAL_AUTH,
+    state::{GlobalConfig, Order},
+    token_operations::lamports_transfer_from_authority_to_account,
+    TipWithdrawn,
+};
+
+pub fn handler_withdraw_maker_tip(ctx: Context<WithdrawMakerTip>) -> Result<()> {
+    let order = &mut ctx.accounts.order.load_mut()?;
+    let global_config = &mut ctx.accounts.global_config.load_mut()?;
+
+    let tip_amount = operations::withdraw_maker_tip(order, global_config)?;
+
+    if tip_amount > 0 {
+        let gc = ctx.accounts.global_config.key();
+        let seeds: &[&[u8]] = global_seeds!(global_config.pda_authority_bump as u8, &gc);
+        lamports_transfer_from_authority_to_account(
+            ctx.accounts.maker.to_account_info(),
+            ctx.accounts.pda_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            seeds,
+            tip_amount,
+        )?;
+    }
+
+    global_config.pda_authority_previous_lamports_balance = ctx.accounts.pda_authority.lamports();
+
+    emit_cpi!(TipWithdrawn {
+        order: ctx.accounts.order.key(),
+        maker: ctx.accounts.maker.key(),
+        tip_amount,
+    });
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawMakerTip<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    #[account(mut,
+        has_one = maker,
+        has_one = global_config,
+    )]
+    pub order: AccountLoader<'info, Order>,
+
+    #[account(
+        mut,
+        has_one = pda_authority,
+    )]
+    pub global_config: AccountLoader<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pda_authority: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
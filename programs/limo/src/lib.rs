@@ -12,10 +12,12 @@ use num_enum::TryFromPrimitive;
 use thiserror::Error;
 use utils::{
     constraints::{
-        create_new_orders_disabled, emergency_mode_disabled, flash_taking_orders_disabled,
-        taking_orders_disabled,
+        create_new_orders_disabled, emergency_mode_disabled, emergency_mode_enabled,
+        flash_taking_orders_disabled, taking_orders_disabled,
+    },
+    consts::{
+        MAX_MULTISIG_SIGNERS, UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE, UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
     },
-    consts::UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
 };
 
 use crate::handlers::*;
@@ -52,6 +54,26 @@ pub mod limo {
         handlers::initialize_vault::handler_initialize_vault(ctx)
     }
 
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn initialize_vault_with_deposit(
+        ctx: Context<InitializeVaultWithDeposit>,
+        input_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+    ) -> Result<()> {
+        handlers::initialize_vault_with_deposit::handler_initialize_vault_with_deposit(
+            ctx,
+            input_amount,
+            output_amount,
+            order_type,
+        )
+    }
+
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        handlers::close_vault::handler_close_vault(ctx)
+    }
+
     #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
     pub fn create_order(
@@ -63,32 +85,263 @@ pub mod limo {
         handlers::create_order::handler_create_order(ctx, input_amount, output_amount, order_type)
     }
 
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn create_order_with_slot_expiry(
+        ctx: Context<CreateOrder>,
+        input_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        handlers::create_order::handler_create_order_with_slot_expiry(
+            ctx,
+            input_amount,
+            output_amount,
+            order_type,
+            expiry_slot,
+        )
+    }
+
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn create_order_with_expiry_timestamp(
+        ctx: Context<CreateOrder>,
+        input_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+        expiry_timestamp: u64,
+    ) -> Result<()> {
+        handlers::create_order::handler_create_order_with_expiry_timestamp(
+            ctx,
+            input_amount,
+            output_amount,
+            order_type,
+            expiry_timestamp,
+        )
+    }
+
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn create_order_with_sequence(
+        ctx: Context<CreateOrderWithSequence>,
+        input_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+    ) -> Result<()> {
+        handlers::create_order_with_sequence::handler_create_order_with_sequence(
+            ctx,
+            input_amount,
+            output_amount,
+            order_type,
+        )
+    }
+
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn create_order_with_ed25519_preauth(
+        ctx: Context<CreateOrderWithEd25519Preauth>,
+        input_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+        nonce: u64,
+    ) -> Result<()> {
+        handlers::create_order_with_ed25519_preauth::handler_create_order_with_ed25519_preauth(
+            ctx,
+            input_amount,
+            output_amount,
+            order_type,
+            nonce,
+        )
+    }
+
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn create_order_with_native_sol(
+        ctx: Context<CreateOrderWithNativeSol>,
+        sol_amount: u64,
+        output_amount: u64,
+        order_type: u8,
+    ) -> Result<()> {
+        handlers::create_order_with_native_sol::handler_create_order_with_native_sol(
+            ctx,
+            sol_amount,
+            output_amount,
+            order_type,
+        )
+    }
+
+    #[access_control(flash_taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
     pub fn update_order(ctx: Context<UpdateOrder>, mode: u16, value: Vec<u8>) -> Result<()> {
         handlers::update_order::handler_update_order(ctx, mode, &value)
     }
 
+    pub fn initialize_maker_output_ata(ctx: Context<InitializeMakerOutputAta>) -> Result<()> {
+        handlers::initialize_maker_output_ata::handler_initialize_maker_output_ata(ctx)
+    }
+
+    pub fn set_order_delegate(ctx: Context<SetOrderDelegate>, delegate: Pubkey) -> Result<()> {
+        handlers::order_delegate::handler_set_order_delegate(ctx, delegate)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn migrate_order_v2(
+        ctx: Context<MigrateOrderV2>,
+        expiry_timestamp: u64,
+        max_fills: u32,
+    ) -> Result<()> {
+        handlers::migrate_order_v2::handler_migrate_order_v2(ctx, expiry_timestamp, max_fills)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn set_order_output_recipient(
+        ctx: Context<SetOrderOutputRecipient>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        handlers::order_output_recipient::handler_set_order_output_recipient(ctx, recipient)
+    }
+
+    pub fn initialize_order_counterparties(
+        ctx: Context<InitializeOrderCounterparties>,
+    ) -> Result<()> {
+        handlers::order_counterparties::handler_initialize_order_counterparties(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn activate_pending_order_update(ctx: Context<ActivatePendingOrderUpdate>) -> Result<()> {
+        handlers::activate_pending_order_update::handler_activate_pending_order_update(ctx)
+    }
+
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
     pub fn close_order_and_claim_tip(ctx: Context<CloseOrderAndClaimTip>) -> Result<()> {
         handlers::close_order_and_claim_tip::handler_close_order_and_claim_tip(ctx)
     }
 
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn cancel_orders_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelOrdersBatch<'info>>,
+        skip_locked_orders: bool,
+    ) -> Result<()> {
+        handlers::cancel_orders_batch::handler_cancel_orders_batch(ctx, skip_locked_orders)
+    }
+
+    #[access_control(create_new_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn split_order(ctx: Context<SplitOrder>, split_input_amount: u64) -> Result<()> {
+        handlers::split_order::handler_split_order(ctx, split_input_amount)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn initialize_dutch_auction(
+        ctx: Context<InitializeDutchAuction>,
+        start_output_amount: u64,
+        duration_seconds: u64,
+    ) -> Result<()> {
+        handlers::dutch_auction::handler_initialize_dutch_auction(
+            ctx,
+            start_output_amount,
+            duration_seconds,
+        )
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn initialize_stop_limit_trigger(
+        ctx: Context<InitializeStopLimitTrigger>,
+        trigger_price_numerator: u64,
+        trigger_price_denominator: u64,
+    ) -> Result<()> {
+        handlers::stop_limit::handler_initialize_stop_limit_trigger(
+            ctx,
+            trigger_price_numerator,
+            trigger_price_denominator,
+        )
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn boost_order_tip(ctx: Context<BoostOrderTip>, boost_lamports: u64) -> Result<()> {
+        handlers::boost_order_tip::handler_boost_order_tip(ctx, boost_lamports)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn propose_order_transfer(ctx: Context<ProposeOrderTransfer>) -> Result<()> {
+        handlers::order_transfer::handler_propose_order_transfer(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn accept_order_transfer(ctx: Context<AcceptOrderTransfer>) -> Result<()> {
+        handlers::order_transfer::handler_accept_order_transfer(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn transfer_order(ctx: Context<TransferOrder>) -> Result<()> {
+        handlers::order_transfer::handler_transfer_order(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn keeper_close_slot_expired_order(
+        ctx: Context<KeeperCloseSlotExpiredOrder>,
+    ) -> Result<()> {
+        handlers::keeper_close_slot_expired_order::handler_keeper_close_slot_expired_order(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn withdraw_maker_tip(ctx: Context<WithdrawMakerTip>) -> Result<()> {
+        handlers::withdraw_maker_tip::handler_withdraw_maker_tip(ctx)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn batch_withdraw_maker_tips<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWithdrawMakerTips<'info>>,
+    ) -> Result<()> {
+        handlers::batch_withdraw_maker_tips::handler_batch_withdraw_maker_tips(ctx)
+    }
+
     #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
-    pub fn take_order(
-        ctx: Context<TakeOrder>,
+    pub fn take_order<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeOrder<'info>>,
         input_amount: u64,
         min_output_amount: u64,
         tip_amount_permissionless_taking: u64,
+        create_maker_ata_if_missing: bool,
     ) -> Result<()> {
         handlers::take_order::handler_take_order(
             ctx,
             input_amount,
             min_output_amount,
             tip_amount_permissionless_taking,
+            create_maker_ata_if_missing,
+        )
+    }
+
+    #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn take_order_with_output_recipient<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeOrder<'info>>,
+        input_amount: u64,
+        min_output_amount: u64,
+        tip_amount_permissionless_taking: u64,
+        output_recipient: Pubkey,
+    ) -> Result<()> {
+        handlers::take_order::handler_take_order_with_output_recipient(
+            ctx,
+            input_amount,
+            min_output_amount,
+            tip_amount_permissionless_taking,
+            output_recipient,
         )
     }
 
+    #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn take_order_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeOrderBatch<'info>>,
+        entries: Vec<TakeOrderBatchEntry>,
+    ) -> Result<()> {
+        handlers::take_order_batch::handler_take_order_batch(ctx, entries)
+    }
+
     #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(flash_taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
@@ -109,8 +362,8 @@ pub mod limo {
     #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(flash_taking_orders_disabled(&ctx.accounts.global_config))]
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
-    pub fn flash_take_order_end(
-        ctx: Context<FlashTakeOrder>,
+    pub fn flash_take_order_end<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashTakeOrder<'info>>,
         input_amount: u64,
         min_output_amount: u64,
         tip_amount_permissionless_taking: u64,
@@ -123,6 +376,40 @@ pub mod limo {
         )
     }
 
+    #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(flash_taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn flash_take_order_v2_start(
+        ctx: Context<FlashTakeOrder>,
+        input_amount: u64,
+        min_output_amount: u64,
+        tip_amount_permissionless_taking: u64,
+    ) -> Result<()> {
+        handlers::flash_take_order::handler_v2_start(
+            ctx,
+            input_amount,
+            min_output_amount,
+            tip_amount_permissionless_taking,
+        )
+    }
+
+    #[access_control(taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(flash_taking_orders_disabled(&ctx.accounts.global_config))]
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn flash_take_order_v2_end<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashTakeOrder<'info>>,
+        input_amount: u64,
+        min_output_amount: u64,
+        tip_amount_permissionless_taking: u64,
+    ) -> Result<()> {
+        handlers::flash_take_order::handler_v2_end(
+            ctx,
+            input_amount,
+            min_output_amount,
+            tip_amount_permissionless_taking,
+        )
+    }
+
     pub fn update_global_config(
         ctx: Context<UpdateGlobalConfig>,
         mode: u16,
@@ -131,15 +418,89 @@ pub mod limo {
         handlers::update_global_config::handler_update_global_config(ctx, mode, &value)
     }
 
+    pub fn update_global_config_v2(
+        ctx: Context<UpdateGlobalConfig>,
+        updates: Vec<GlobalConfigUpdateEntry>,
+    ) -> Result<()> {
+        handlers::update_global_config::handler_update_global_config_v2(ctx, updates)
+    }
+
+    pub fn initialize_multisig_admin(
+        ctx: Context<InitializeMultisigAdmin>,
+        signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+        threshold: u8,
+    ) -> Result<()> {
+        handlers::multisig_admin::handler_initialize_multisig_admin(ctx, signers, threshold)
+    }
+
+    pub fn propose_global_config_update(
+        ctx: Context<ProposeGlobalConfigUpdate>,
+        mode: u16,
+        value: [u8; UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE],
+    ) -> Result<()> {
+        handlers::multisig_admin::handler_propose_global_config_update(ctx, mode, value)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        handlers::multisig_admin::handler_approve_proposal(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        handlers::multisig_admin::handler_execute_proposal(ctx)
+    }
+
+    pub fn close_expired_proposal(ctx: Context<CloseExpiredProposal>) -> Result<()> {
+        handlers::multisig_admin::handler_close_expired_proposal(ctx)
+    }
+
+    pub fn sweep_dust(ctx: Context<SweepDust>, recipient: Pubkey) -> Result<()> {
+        handlers::sweep_dust::handler_sweep_dust(ctx, recipient)
+    }
+
+    pub fn recalculate_ata_creation_cost(ctx: Context<RecalculateAtaCreationCost>) -> Result<()> {
+        handlers::recalculate_ata_creation_cost::handler_recalculate_ata_creation_cost(ctx)
+    }
+
+    pub fn snapshot_global_config(ctx: Context<SnapshotGlobalConfig>) -> Result<()> {
+        handlers::snapshot_global_config::handler_snapshot_global_config(ctx)
+    }
+
     pub fn update_global_config_admin(ctx: Context<UpdateGlobalConfigAdmin>) -> Result<()> {
         handlers::update_global_config_admin::handler_update_global_config_admin(ctx)
     }
 
+    #[access_control(emergency_mode_enabled(&ctx.accounts.global_config))]
+    pub fn cleanup_stuck_intermediary_account(
+        ctx: Context<CleanupStuckIntermediaryAccount>,
+    ) -> Result<()> {
+        handlers::cleanup_stuck_intermediary_account::handler_cleanup_stuck_intermediary_account(
+            ctx,
+        )
+    }
+
+    #[access_control(emergency_mode_enabled(&ctx.accounts.global_config))]
+    pub fn admin_force_cancel_order(ctx: Context<AdminForceCancelOrder>) -> Result<()> {
+        handlers::admin_force_cancel_order::handler_admin_force_cancel_order(ctx)
+    }
+
+    #[access_control(emergency_mode_enabled(&ctx.accounts.global_config))]
+    pub fn bulk_close_empty_vaults<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BulkCloseEmptyVaults<'info>>,
+    ) -> Result<()> {
+        handlers::bulk_close_empty_vaults::handler_bulk_close_empty_vaults(ctx)
+    }
+
     #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
     pub fn withdraw_host_tip(ctx: Context<WithdrawHostTip>) -> Result<()> {
         handlers::withdraw_host_tip::withdraw_host_tip(ctx)
     }
 
+    pub fn batch_withdraw_host_tip<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWithdrawHostTip<'info>>,
+    ) -> Result<()> {
+        handlers::batch_withdraw_host_tip::handler_batch_withdraw_host_tip(ctx)
+    }
+
     pub fn log_user_swap_balances_start(
         ctx: Context<LogUserSwapBalancesStartContext>,
     ) -> Result<()> {
@@ -180,13 +541,130 @@ pub mod limo {
         ctx: Context<AssertUserSwapBalancesEndContext>,
         max_input_amount_change: u64,
         min_output_amount_change: u64,
+        max_lamports_loss: u64,
     ) -> Result<()> {
         handlers::assert_user_swap_balances::handler_assert_user_swap_balances_end(
             ctx,
             max_input_amount_change,
             min_output_amount_change,
+            max_lamports_loss,
+        )
+    }
+
+    pub fn assert_user_swap_balances_v2_start(
+        ctx: Context<AssertUserSwapBalancesV2StartContext>,
+    ) -> Result<()> {
+        handlers::assert_user_swap_balances::handler_assert_user_swap_balances_v2_start(ctx)
+    }
+
+    pub fn assert_user_swap_balances_v2_end(
+        ctx: Context<AssertUserSwapBalancesV2EndContext>,
+        max_slippage_input_bps: u16,
+        min_output_improvement_bps: u16,
+    ) -> Result<()> {
+        handlers::assert_user_swap_balances::handler_assert_user_swap_balances_v2_end(
+            ctx,
+            max_slippage_input_bps,
+            min_output_improvement_bps,
+        )
+    }
+
+    pub fn assert_price_within_bounds(
+        ctx: Context<AssertPriceWithinBounds>,
+        expected_price_numerator: u64,
+        expected_price_denominator: u64,
+        tolerance_bps: u16,
+    ) -> Result<()> {
+        handlers::assert_price_within_bounds::handler_assert_price_within_bounds(
+            ctx,
+            expected_price_numerator,
+            expected_price_denominator,
+            tolerance_bps,
         )
     }
+
+    pub fn log_pending_fills<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LogPendingFills<'info>>,
+    ) -> Result<()> {
+        handlers::log_pending_fills::handler_log_pending_fills(ctx)
+    }
+
+    pub fn log_order_fill_rate(ctx: Context<LogOrderFillRate>) -> Result<()> {
+        handlers::log_order_fill_rate::handler_log_order_fill_rate(ctx)
+    }
+
+    pub fn initialize_vwap_oracle(
+        ctx: Context<InitializeVwapOracle>,
+        window_seconds: u64,
+    ) -> Result<()> {
+        handlers::vwap_oracle::handler_initialize_vwap_oracle(ctx, window_seconds)
+    }
+
+    pub fn read_vwap_oracle(ctx: Context<ReadVwapOracle>) -> Result<()> {
+        handlers::vwap_oracle::handler_read_vwap_oracle(ctx)
+    }
+
+    pub fn initialize_order_book_snapshot(ctx: Context<InitializeOrderBookSnapshot>) -> Result<()> {
+        handlers::order_book_snapshot::handler_initialize_order_book_snapshot(ctx)
+    }
+
+    pub fn update_order_book_snapshot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateOrderBookSnapshot<'info>>,
+    ) -> Result<()> {
+        handlers::order_book_snapshot::handler_update_order_book_snapshot(ctx)
+    }
+
+    pub fn initialize_maker_analytics(ctx: Context<InitializeMakerAnalytics>) -> Result<()> {
+        handlers::maker_analytics::handler_initialize_maker_analytics(ctx)
+    }
+
+    pub fn initialize_maker_volume(ctx: Context<InitializeMakerVolume>, epoch: u64) -> Result<()> {
+        handlers::maker_volume::handler_initialize_maker_volume(ctx, epoch)
+    }
+
+    #[access_control(emergency_mode_disabled(&ctx.accounts.global_config))]
+    pub fn claim_volume_rebate(ctx: Context<ClaimVolumeRebate>, epoch: u64) -> Result<()> {
+        handlers::maker_volume::handler_claim_volume_rebate(ctx, epoch)
+    }
+
+    pub fn initialize_mint_fee_config(
+        ctx: Context<InitializeMintFeeConfig>,
+        host_fee_bps: u16,
+        enabled: u8,
+    ) -> Result<()> {
+        handlers::mint_fee_config::handler_initialize_mint_fee_config(ctx, host_fee_bps, enabled)
+    }
+
+    pub fn update_mint_fee_config(
+        ctx: Context<UpdateMintFeeConfig>,
+        host_fee_bps: u16,
+        enabled: u8,
+    ) -> Result<()> {
+        handlers::mint_fee_config::handler_update_mint_fee_config(ctx, host_fee_bps, enabled)
+    }
+
+    pub fn set_pair_oracle_config(
+        ctx: Context<SetPairOracleConfig>,
+        price_feed_id: [u8; 32],
+    ) -> Result<()> {
+        handlers::pair_oracle_config::handler_set_pair_oracle_config(ctx, price_feed_id)
+    }
+
+    pub fn register_allowed_program(ctx: Context<RegisterAllowedProgram>) -> Result<()> {
+        handlers::allowed_program::handler_register_allowed_program(ctx)
+    }
+
+    pub fn deregister_allowed_program(ctx: Context<DeregisterAllowedProgram>) -> Result<()> {
+        handlers::allowed_program::handler_deregister_allowed_program(ctx)
+    }
+
+    pub fn register_swap_program(ctx: Context<RegisterSwapProgram>) -> Result<()> {
+        handlers::swap_program_registry::handler_register_swap_program(ctx)
+    }
+
+    pub fn deregister_swap_program(ctx: Context<DeregisterSwapProgram>) -> Result<()> {
+        handlers::swap_program_registry::handler_deregister_swap_program(ctx)
+    }
 }
 
 #[error_code]
@@ -297,7 +775,7 @@ pub enum LimoError {
     #[msg("Not enough balance for rent")]
     NotEnoughBalanceForRent,
 
-    #[msg("Order can not be closed - Not enough time passed since last update")]
+    #[msg("Order not old enough to close")]
     NotEnoughTimePassedSinceLastUpdate,
 
     #[msg("Order input and output mints are the same")]
@@ -344,6 +822,168 @@ pub enum LimoError {
 
     #[msg("The swap output balance change is negative, expected positive")]
     SwapOutputInvalidBalanceChange,
+
+    #[msg("Vwap oracle account does not match the order")]
+    InvalidVwapOracle,
+
+    #[msg("Invalid number of orders in batch")]
+    InvalidBatchSize,
+
+    #[msg("Order does not belong to the global config")]
+    InvalidOrderGlobalConfig,
+
+    #[msg("Order has no pending update")]
+    NoPendingOrderUpdate,
+
+    #[msg("Pending order update can not be activated yet")]
+    PendingOrderUpdateNotReady,
+
+    #[msg("Failed to create maker output ata")]
+    MakerOutputAtaCreationFailed,
+
+    #[msg("Maker input ata required when input mint is not WSOL")]
+    MakerInputAtaRequired,
+
+    #[msg("Temporary WSOL token account and rent sysvar required when input mint is WSOL")]
+    TempWsolTokenAccountRequired,
+
+    #[msg("Order has expired")]
+    OrderExpired,
+
+    #[msg("Order has not expired yet")]
+    OrderNotExpired,
+
+    #[msg("Expiry slot must be in the future")]
+    InvalidExpirySlot,
+
+    #[msg("Emergency mode must be enabled")]
+    EmergencyModeDisabled,
+
+    #[msg("Too many intermediary instructions between flash instructions")]
+    FlashTxTooComplex,
+
+    #[msg("None of the orders are flash locked")]
+    NoLockedOrders,
+
+    #[msg("Vault still holds tokens")]
+    VaultNotEmpty,
+
+    #[msg("Order transfer finalization delay has not passed yet")]
+    OrderTransferNotReady,
+
+    #[msg("Intermediary account was not cleaned up after a previous fill")]
+    StuckIntermediaryAccount,
+
+    #[msg("Order type is deprecated")]
+    OrderTypeDeprecated,
+
+    #[msg("Missing or invalid ed25519 order preauthorization")]
+    InvalidEd25519Preauthorization,
+
+    #[msg("Maker nonce does not match the expected one")]
+    InvalidMakerNonce,
+
+    #[msg("Output amount is below the current Dutch auction price")]
+    DutchAuctionPriceNotMet,
+
+    #[msg("Input mint must be wrapped SOL")]
+    InputMintMustBeWsol,
+
+    #[msg("Invalid oracle price account")]
+    InvalidOraclePriceAccount,
+
+    #[msg("Fill price deviates from the oracle price by more than the allowed tolerance")]
+    OraclePriceBoundExceeded,
+
+    #[msg("Maker reached the maximum number of open orders")]
+    MakerOrderLimitReached,
+
+    #[msg("Tip is below the configured minimum")]
+    TipBelowMinimum,
+
+    #[msg("User lost more lamports than allowed during the swap")]
+    ExcessiveLamportsLoss,
+
+    #[msg("Invalid maker volume account")]
+    InvalidMakerVolume,
+
+    #[msg("Volume rebate is not claimable")]
+    VolumeRebateNotClaimable,
+
+    #[msg("Global config updates must go through a multisig proposal")]
+    MultisigEnabled,
+
+    #[msg("Signer is not a member of the multisig admin")]
+    InvalidMultisigSigner,
+
+    #[msg("Invalid multisig threshold")]
+    InvalidMultisigThreshold,
+
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+
+    #[msg("Proposal has not expired yet")]
+    ProposalNotExpired,
+
+    #[msg("Proposal does not have enough approvals")]
+    ProposalThresholdNotMet,
+
+    #[msg("Either the maker or the order delegate must sign")]
+    MakerOrDelegateSignatureRequired,
+
+    #[msg("Sum of order remaining input amounts overflows")]
+    VaultDustCalculationOverflow,
+
+    #[msg("Swap program registry is full")]
+    SwapProgramRegistryFull,
+
+    #[msg("Swap program is not registered")]
+    SwapProgramNotRegistered,
+
+    #[msg("Order counterparties account is required for this update")]
+    OrderCounterpartiesRequired,
+
+    #[msg("Oracle price has not reached the stop-limit trigger price")]
+    StopPriceNotMet,
+
+    #[msg("Invalid maker analytics account")]
+    InvalidMakerAnalytics,
+
+    #[msg("Counterparty cannot be the default key or the PDA authority")]
+    InvalidCounterparty,
+
+    #[msg("Invalid order output recipient")]
+    InvalidOutputRecipient,
+
+    #[msg("Tip is below the ata creation cost credited to the taker")]
+    TipBelowAtaCreationCost,
+
+    #[msg("Expiry timestamp must be in the future")]
+    InvalidExpiryTimestamp,
+
+    #[msg("Invalid referrer fee")]
+    InvalidReferrerFee,
+
+    #[msg("Orders with a referrer can not be taken in a batch")]
+    ReferredOrderNotSupportedInBatch,
+
+    #[msg("Order reached its maximum number of fills")]
+    MaxFillsReached,
+
+    #[msg("Pyth price account is required while oracle price bounds are configured")]
+    OraclePriceAccountRequired,
+
+    #[msg("Invalid oracle price tolerance")]
+    InvalidOraclePriceTolerance,
+
+    #[msg("Order can only be taken by its counterparty during the exclusive taker window")]
+    ExclusiveTakerWindowActive,
+
+    #[msg("No oracle price feed is configured for the order's mint pair")]
+    OraclePriceFeedNotConfigured,
+
+    #[msg("Enabling the multisig requires an initialized multisig admin")]
+    MultisigAdminRequired,
 }
 
 impl From<TryFromIntError> for LimoError {
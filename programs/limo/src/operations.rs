@@ -2,14 +2,20 @@
 use std::cmp;
 
 use anchor_lang::prelude::*;
-use solana_program::clock;
+use solana_program::{clock, hash::hashv};
 
 use crate::{
     dbg_msg, require_lte,
     state::*,
     utils::{
-        consts::UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
+        constraints::is_counterparty_matching,
+        consts::{
+            CURRENT_PROTOCOL_VERSION, DEFAULT_MAX_FLASH_INTERMEDIARY_IXS, FEE_TIER_PREMIUM,
+            FULL_BPS, HOST_FEE_ROUNDING_MODE_FLOOR, MAX_MULTISIG_SIGNERS,
+            UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE, UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
+        },
         fraction::{Fraction, FractionExtra},
+        oracle::{is_oracle_price_at_or_above, OraclePrice},
     },
     LimoError,
 };
@@ -26,9 +32,13 @@ pub fn initialize_global_config(
     global_config.pda_authority_bump = pda_bump;
     global_config.admin_authority = admin_authority;
     global_config.admin_authority_cached = admin_authority;
+    global_config.fee_collector = admin_authority;
     global_config.total_tip_amount = 0;
     global_config.host_tip_amount = 0;
     global_config.pda_authority_previous_lamports_balance = pda_authority_previous_lamports_balance;
+    global_config.express_relay_disabled = 0;
+    global_config.max_flash_intermediary_ixs = DEFAULT_MAX_FLASH_INTERMEDIARY_IXS;
+    global_config.protocol_version = CURRENT_PROTOCOL_VERSION;
 }
 
 pub fn create_order(
@@ -44,6 +54,10 @@ pub fn create_order(
     order_type: u8,
     in_vault_bump: u8,
     current_timestamp: i64,
+    current_slot: u64,
+    expiry_slot: u64,
+    expiry_timestamp: u64,
+    order_close_delay_seconds: u64,
 ) -> Result<()> {
     order.global_config = global_config;
     order.initial_input_amount = input_amount;
@@ -60,13 +74,99 @@ pub fn create_order(
     order.order_type = order_type;
     order.in_vault_bump = in_vault_bump;
     order.last_updated_timestamp = current_timestamp.try_into().expect("Negative timestamp");
+    order.created_timestamp = order.last_updated_timestamp;
     order.counterparty = Pubkey::default();
     order.permissionless = 0;
+    order.expiry_slot = expiry_slot;
+    order.expiry_timestamp = expiry_timestamp;
+    order.nonce = current_slot;
+    order.close_delay_at_creation = order_close_delay_seconds;
+
+    #[cfg(any(test, feature = "test-bpf"))]
+    debug_assert_order_invariants(order);
+
+    Ok(())
+}
+
+pub fn split_order(
+    order: &mut Order,
+    new_order: &mut Order,
+    global_config: &mut GlobalConfig,
+    split_input_amount: u64,
+    current_timestamp: i64,
+    current_slot: u64,
+) -> Result<()> {
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+    require!(
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+    require!(
+        split_input_amount > 0 && split_input_amount < order.remaining_input_amount,
+        LimoError::OrderInputAmountInvalid
+    );
+
+    let split_output_amount = minimum_output_for_input(order, split_input_amount)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    require!(
+        split_output_amount > 0 && split_output_amount < order.expected_output_amount,
+        LimoError::OrderOutputAmountInvalid
+    );
+
+    order.remaining_input_amount -= split_input_amount;
+    order.initial_input_amount -= split_input_amount;
+    order.expected_output_amount -= split_output_amount;
+
+    create_order(
+        new_order,
+        order.global_config,
+        order.maker,
+        split_input_amount,
+        split_output_amount,
+        order.input_mint,
+        order.output_mint,
+        order.input_mint_program_id,
+        order.output_mint_program_id,
+        order.order_type,
+        order.in_vault_bump,
+        current_timestamp,
+        current_slot,
+        order.expiry_slot,
+        order.expiry_timestamp,
+        order.close_delay_at_creation,
+    )?;
+    new_order.permissionless = order.permissionless;
+    new_order.immutable_once_partially_filled = order.immutable_once_partially_filled;
+    new_order.fee_tier = order.fee_tier;
+    new_order.express_relay_only = order.express_relay_only;
+    new_order.counterparty = order.counterparty;
+    new_order.max_fills = order.max_fills;
+    new_order.exclusive_taker_window_seconds = order.exclusive_taker_window_seconds;
+
+    global_config.open_order_count = global_config.open_order_count.saturating_add(1);
+
+    #[cfg(any(test, feature = "test-bpf"))]
+    debug_assert_order_invariants(order);
 
     Ok(())
 }
 
-pub fn update_order(order: &mut Order, mode: UpdateOrderMode, value: &[u8]) -> Result<()> {
+pub fn update_order(
+    order: &mut Order,
+    global_config: &GlobalConfig,
+    order_counterparties: Option<&mut OrderCounterparties>,
+    mode: UpdateOrderMode,
+    value: &[u8],
+    current_slot: u64,
+) -> Result<()> {
+    require!(
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+
     match mode {
         UpdateOrderMode::UpdatePermissionless => {
             require!(value.len() == 1, LimoError::InvalidParameterType);
@@ -77,22 +177,184 @@ pub fn update_order(order: &mut Order, mode: UpdateOrderMode, value: &[u8]) -> R
         UpdateOrderMode::UpdateCounterparty => {
             require!(value.len() == 32, LimoError::InvalidParameterType);
             msg!("update_order mode={:?}", mode);
-            msg!("new={:?} prev={}", &value[..32], order.counterparty);
-            order.counterparty = Pubkey::new_from_array(
+            let counterparty = Pubkey::new_from_array(
+                value[..32]
+                    .try_into()
+                    .map_err(|_| LimoError::InvalidParameterType)?,
+            );
+            // The default pubkey clears the counterparty
+            require!(
+                counterparty != global_config.pda_authority,
+                LimoError::InvalidCounterparty
+            );
+            msg!("new={} prev={}", counterparty, order.counterparty);
+            order.counterparty = counterparty;
+        }
+        UpdateOrderMode::UpdateExpectedOutputAmount => {
+            require!(value.len() == 8, LimoError::InvalidParameterType);
+            let value = u64::from_le_bytes(
+                value[..8]
+                    .try_into()
+                    .map_err(|_| LimoError::InvalidParameterType)?,
+            );
+            require!(value > 0, LimoError::OrderOutputAmountInvalid);
+            require!(
+                value >= order.filled_output_amount,
+                LimoError::OrderOutputAmountInvalid
+            );
+            require!(
+                order.flash_ix_lock == 0,
+                LimoError::OrderWithinFlashOperation
+            );
+            msg!("update_order mode={:?}", mode);
+            if global_config.order_update_delay_slots == 0 {
+                msg!("new={} prev={}", value, order.expected_output_amount);
+                order.expected_output_amount = value;
+                order.pending_output_amount = 0;
+                order.pending_update_slot = 0;
+            } else {
+                let pending_update_slot = current_slot
+                    .checked_add(global_config.order_update_delay_slots)
+                    .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+                msg!(
+                    "pending={} prev={} activation_slot={}",
+                    value,
+                    order.expected_output_amount,
+                    pending_update_slot
+                );
+                order.pending_output_amount = value;
+                order.pending_update_slot = pending_update_slot;
+            }
+        }
+        UpdateOrderMode::SetImmutableOncePartiallyFilled => {
+            require!(
+                value.len() == 1 && value[0] == 1,
+                LimoError::InvalidParameterType
+            );
+            msg!("update_order mode={:?}", mode);
+            msg!(
+                "new={} prev={}",
+                value[0],
+                order.immutable_once_partially_filled
+            );
+            order.immutable_once_partially_filled = value[0];
+        }
+        UpdateOrderMode::UpdateMakerNote => {
+            require!(value.len() == 32, LimoError::InvalidParameterType);
+            msg!("update_order mode={:?}", mode);
+            order.maker_note = value[..32]
+                .try_into()
+                .map_err(|_| LimoError::InvalidParameterType)?;
+            msg!("new={}", order.maker_note_hex());
+        }
+        UpdateOrderMode::SetExpressRelayOnly => {
+            require!(
+                value.len() == 1 && value[0] <= 1,
+                LimoError::InvalidParameterType
+            );
+            msg!("update_order mode={:?}", mode);
+            msg!("new={} prev={}", value[0], order.express_relay_only);
+            order.express_relay_only = value[0];
+        }
+        UpdateOrderMode::UpdateTags => {
+            require!(value.len() == 16, LimoError::InvalidParameterType);
+            msg!("update_order mode={:?}", mode);
+            msg!("prev={}", order.tags_hex());
+            order.tags = value[..16]
+                .try_into()
+                .map_err(|_| LimoError::InvalidParameterType)?;
+            msg!("new={}", order.tags_hex());
+        }
+        UpdateOrderMode::UpdateAdditionalCounterparty0
+        | UpdateOrderMode::UpdateAdditionalCounterparty1
+        | UpdateOrderMode::UpdateAdditionalCounterparty2 => {
+            require!(value.len() == 32, LimoError::InvalidParameterType);
+            let order_counterparties =
+                order_counterparties.ok_or(LimoError::OrderCounterpartiesRequired)?;
+            let idx = mode as usize - UpdateOrderMode::UpdateAdditionalCounterparty0 as usize;
+            let value = Pubkey::new_from_array(
                 value[..32]
                     .try_into()
                     .map_err(|_| LimoError::InvalidParameterType)?,
             );
+            require!(
+                value != global_config.pda_authority,
+                LimoError::InvalidCounterparty
+            );
+            msg!("update_order mode={:?}", mode);
+            msg!(
+                "new={} prev={}",
+                value,
+                order_counterparties.additional_counterparties[idx]
+            );
+            order_counterparties.additional_counterparties[idx] = value;
+        }
+        UpdateOrderMode::UpdateMaxFills => {
+            require!(value.len() == 4, LimoError::InvalidParameterType);
+            let value = u32::from_le_bytes(
+                value[..4]
+                    .try_into()
+                    .map_err(|_| LimoError::InvalidParameterType)?,
+            );
+            msg!("update_order mode={:?}", mode);
+            msg!("new={} prev={}", value, order.max_fills);
+            order.max_fills = value;
+        }
+        UpdateOrderMode::UpdateExclusiveTakerWindowSeconds => {
+            require!(value.len() == 4, LimoError::InvalidParameterType);
+            let value = u32::from_le_bytes(
+                value[..4]
+                    .try_into()
+                    .map_err(|_| LimoError::InvalidParameterType)?,
+            );
+            msg!("update_order mode={:?}", mode);
+            msg!(
+                "new={} prev={}",
+                value,
+                order.exclusive_taker_window_seconds
+            );
+            order.exclusive_taker_window_seconds = value;
         }
     }
     Ok(())
 }
 
+pub fn activate_pending_order_update(order: &mut Order, current_slot: u64) -> Result<()> {
+    require!(
+        order.pending_update_slot > 0,
+        LimoError::NoPendingOrderUpdate
+    );
+    require!(
+        current_slot >= order.pending_update_slot,
+        LimoError::PendingOrderUpdateNotReady
+    );
+    require!(
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+    require!(
+        order.pending_output_amount >= order.filled_output_amount,
+        LimoError::OrderOutputAmountInvalid
+    );
+
+    msg!(
+        "new={} prev={}",
+        order.pending_output_amount,
+        order.expected_output_amount
+    );
+    order.expected_output_amount = order.pending_output_amount;
+    order.pending_output_amount = 0;
+    order.pending_update_slot = 0;
+
+    Ok(())
+}
+
 pub fn validate_user_swap_balances(
     start_balance_state: &UserSwapBalancesState,
     end_balance_state: GetBalancesCheckedResult,
     max_input_amount_change: u64,
     min_output_amount_change: u64,
+    max_lamports_loss: u64,
 ) -> Result<()> {
     require_gte!(
         start_balance_state.input_ta_balance,
@@ -116,6 +378,50 @@ pub fn validate_user_swap_balances(
         min_output_amount_change,
         LimoError::SwapOutputAmountTooSmall
     );
+    require_gte!(
+        max_lamports_loss,
+        start_balance_state
+            .user_lamports
+            .saturating_sub(end_balance_state.lamports_balance),
+        LimoError::ExcessiveLamportsLoss
+    );
+    Ok(())
+}
+
+pub fn relative_user_swap_balance_bounds(
+    start_balance_state: &UserSwapBalancesState,
+    max_slippage_input_bps: u16,
+    min_output_improvement_bps: u16,
+) -> (u64, u64) {
+    let max_input_amount_change = u128::from(start_balance_state.input_ta_balance)
+        * u128::from(max_slippage_input_bps)
+        / u128::from(FULL_BPS);
+    let min_output_amount_change = (u128::from(start_balance_state.output_ta_balance)
+        * u128::from(min_output_improvement_bps))
+    .div_ceil(u128::from(FULL_BPS));
+
+    (
+        u64::try_from(max_input_amount_change).unwrap_or(u64::MAX),
+        u64::try_from(min_output_amount_change).unwrap_or(u64::MAX),
+    )
+}
+
+/// Enforces the maker close cooldown once per close instruction, however many
+/// orders it closes
+pub fn record_maker_close(
+    global_config: &GlobalConfig,
+    maker_activity: &mut MakerActivity,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(
+        current_timestamp
+            >= maker_activity
+                .last_close_timestamp
+                .saturating_add(global_config.maker_close_cooldown_seconds),
+        LimoError::NotEnoughTimePassedSinceLastUpdate
+    );
+    maker_activity.last_close_timestamp = current_timestamp;
+
     Ok(())
 }
 
@@ -124,227 +430,1524 @@ pub fn close_order_and_claim_tip(
     global_config: &mut GlobalConfig,
     current_timestamp: u64,
 ) -> Result<()> {
+    let is_cancelled_immediate_or_cancel = order.status == OrderStatus::Cancelled as u8
+        && order.order_type == u8::from(OrderType::ImmediateOrCancel);
+    let is_expired = order.status == OrderStatus::Expired as u8;
+    let is_already_closed_out = is_cancelled_immediate_or_cancel || is_expired;
+    let is_filled = order.status == OrderStatus::Filled as u8;
+
     require!(
-        order.status == OrderStatus::Active as u8 || order.status == OrderStatus::Filled as u8,
+        order.status == OrderStatus::Active as u8 || is_filled || is_already_closed_out,
         LimoError::OrderCanNotBeCanceled
     );
 
     require!(
-        current_timestamp >= order.last_updated_timestamp + global_config.order_close_delay_seconds,
-        LimoError::NotEnoughTimePassedSinceLastUpdate
+        order.immutable_once_partially_filled == 0
+            || order.number_of_fills == 0
+            || order.status != OrderStatus::Active as u8,
+        LimoError::OrderCanNotBeCanceled
     );
 
+    if !is_already_closed_out && !is_filled {
+        // Orders created before the delay was recorded read zero and use the global value
+        let close_delay_seconds = match order.close_delay_at_creation {
+            0 => global_config.order_close_delay_seconds,
+            close_delay_at_creation => cmp::min(
+                global_config.order_close_delay_seconds,
+                close_delay_at_creation,
+            ),
+        };
+        require!(
+            current_timestamp >= order.last_updated_timestamp + close_delay_seconds,
+            LimoError::NotEnoughTimePassedSinceLastUpdate
+        );
+
+        if global_config.min_order_lifetime_seconds > 0 {
+            require!(
+                current_timestamp
+                    >= order
+                        .created_timestamp
+                        .saturating_add(global_config.min_order_lifetime_seconds),
+                LimoError::NotEnoughTimePassedSinceLastUpdate
+            );
+        }
+    }
+
     require!(
         order.flash_ix_lock == 0,
         LimoError::OrderWithinFlashOperation
     );
 
-    order.status = OrderStatus::Cancelled as u8;
+    if !is_expired {
+        order.status = OrderStatus::Cancelled as u8;
+    }
+
+    global_config.total_tip_amount -= order.tip_amount + order.tip_boost_lamports;
+    if !is_already_closed_out {
+        global_config.open_order_count = global_config.open_order_count.saturating_sub(1);
+    }
 
-    global_config.total_tip_amount -= order.tip_amount;
+    #[cfg(any(test, feature = "test-bpf"))]
+    {
+        debug_assert_order_invariants(order);
+        debug_assert_global_config_invariants(global_config);
+    }
 
     Ok(())
 }
 
-pub fn withdraw_host_tip(
+pub fn keeper_close_slot_expired_order(
+    order: &mut Order,
     global_config: &mut GlobalConfig,
-    pda_authority_balance: u64,
-) -> Result<u64> {
-    require_gte!(
-        pda_authority_balance,
-        global_config.host_tip_amount,
-        LimoError::InvalidHostTipBalance
+    current_slot: clock::Slot,
+    current_timestamp: u64,
+) -> Result<()> {
+    let was_expired = order.status == OrderStatus::Expired as u8;
+    require!(
+        order.status == OrderStatus::Active as u8
+            || order.status == OrderStatus::Filled as u8
+            || was_expired,
+        LimoError::OrderCanNotBeCanceled
     );
-    let host_tip_amount = global_config.host_tip_amount;
-    global_config.total_tip_amount -= host_tip_amount;
-    global_config.host_tip_amount = 0;
-    Ok(host_tip_amount)
-}
 
-pub fn flash_withdraw_order_input(
-    order: &mut Order,
-    input_amount: u64,
-    output_amount: u64,
-) -> Result<TakeOrderEffects> {
-    let TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    } = take_order_calcs(order, input_amount, output_amount)?;
+    require!(
+        order.is_expired(current_slot, current_timestamp),
+        LimoError::OrderNotExpired
+    );
 
     require!(
         order.flash_ix_lock == 0,
         LimoError::OrderWithinFlashOperation
     );
 
-    order.flash_ix_lock = 1;
-    Ok(TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    })
+    order.status = OrderStatus::Expired as u8;
+
+    global_config.total_tip_amount = global_config
+        .total_tip_amount
+        .checked_sub(order.tip_amount + order.tip_boost_lamports)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    if !was_expired {
+        global_config.open_order_count = global_config.open_order_count.saturating_sub(1);
+    }
+
+    Ok(())
 }
 
-pub fn flash_pay_order_output(
-    global_config: &mut GlobalConfig,
+pub fn migrate_order_v2(
     order: &mut Order,
-    input_amount: u64,
-    output_amount: u64,
-    tip_amount: u64,
-    current_timestamp: clock::UnixTimestamp,
-) -> Result<TakeOrderEffects> {
-    let TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    } = take_order_calcs(order, input_amount, output_amount)?;
-
+    global_config: &GlobalConfig,
+    expiry_timestamp: u64,
+    max_fills: u32,
+    current_timestamp: u64,
+) -> Result<()> {
     require!(
-        order.flash_ix_lock == 1,
-        LimoError::OrderNotWithinFlashOperation
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
     );
 
-    update_take_order_accounting_and_tips(
-        global_config,
-        order,
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-        tip_amount,
-        current_timestamp,
-    )?;
+    // Only fields still at their zero default are written, so a migration
+    // can never loosen what the order was created with
+    if order.expiry_timestamp == 0 && expiry_timestamp != 0 {
+        require!(
+            expiry_timestamp > current_timestamp,
+            LimoError::InvalidExpiryTimestamp
+        );
+        order.expiry_timestamp = expiry_timestamp;
+    }
+    if order.max_fills == 0 {
+        order.max_fills = max_fills;
+    }
 
-    order.flash_ix_lock = 0;
-    Ok(TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    })
-}
+    // The last update is never earlier than the real creation time, and the
+    // current delay is what legacy orders already fall back to
+    if order.created_timestamp == 0 {
+        order.created_timestamp = order.last_updated_timestamp;
+    }
+    if order.close_delay_at_creation == 0 {
+        order.close_delay_at_creation = global_config.order_close_delay_seconds;
+    }
 
-pub fn take_order_calcs(
-    order: &Order,
-    input_amount: u64,
-    output_amount: u64,
-) -> Result<TakeOrderEffects> {
-    require!(input_amount > 0, LimoError::OrderInputAmountInvalid);
+    Ok(())
+}
 
+pub fn admin_force_cancel_order(order: &mut Order, global_config: &mut GlobalConfig) -> Result<()> {
+    // Expired and cancelled immediate-or-cancel orders can still hold escrow and tips
     require!(
-        order.status == OrderStatus::Active as u8,
-        LimoError::OrderNotActive
+        order.remaining_input_amount > 0 || order.tip_amount + order.tip_boost_lamports > 0,
+        LimoError::OrderCanNotBeCanceled
     );
 
     require!(
-        input_amount <= order.remaining_input_amount,
-        LimoError::OrderInputAmountTooLarge
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
     );
 
-    let input_to_send_to_taker = input_amount;
-    let minimum_output_to_send_to_maker_u128 = (u128::from(input_to_send_to_taker)
-        * u128::from(order.expected_output_amount))
-    .div_ceil(u128::from(order.initial_input_amount));
-
-    let minimum_output_to_send_to_maker = u64::try_from(minimum_output_to_send_to_maker_u128)
-        .map_err(|_| dbg_msg!(LimoError::MathOverflow))?;
-
-    let output_to_send_to_maker = cmp::max(output_amount, minimum_output_to_send_to_maker);
-
-    if output_to_send_to_maker != output_amount {
-        msg!("output_amount: {}", output_amount);
-        msg!(
-            "minimum_output_to_send_to_maker: {}",
-            minimum_output_to_send_to_maker
-        );
-        return err!(LimoError::OrderOutputAmountInvalid);
+    let is_open =
+        order.status == OrderStatus::Active as u8 || order.status == OrderStatus::Filled as u8;
+    if is_open {
+        order.status = OrderStatus::Cancelled as u8;
+        global_config.open_order_count = global_config.open_order_count.saturating_sub(1);
     }
 
-    msg!("input_to_send_to_taker: {}", input_to_send_to_taker);
-    msg!("output_to_send_to_maker: {}", output_to_send_to_maker);
+    global_config.total_tip_amount = global_config
+        .total_tip_amount
+        .checked_sub(order.tip_amount + order.tip_boost_lamports)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
 
-    Ok(TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    })
+    Ok(())
 }
 
-pub fn take_order(
-    global_config: &mut GlobalConfig,
-    order: &mut Order,
-    input_amount: u64,
-    tip_amount: u64,
-    current_timestamp: clock::UnixTimestamp,
-    output_amount: u64,
-) -> Result<TakeOrderEffects> {
+pub fn withdraw_maker_tip(order: &mut Order, global_config: &mut GlobalConfig) -> Result<u64> {
     require!(
         order.flash_ix_lock == 0,
         LimoError::OrderWithinFlashOperation
     );
 
-    let TakeOrderEffects {
-        input_to_send_to_taker,
-        output_to_send_to_maker,
-    } = take_order_calcs(order, input_amount, output_amount)?;
+    let maker_tip_amount = order.tip_amount;
+    global_config.total_tip_amount = global_config
+        .total_tip_amount
+        .checked_sub(maker_tip_amount)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    order.tip_amount = 0;
+
+    Ok(maker_tip_amount)
+}
+
+pub fn boost_order_tip(
+    order: &mut Order,
+    global_config: &mut GlobalConfig,
+    boost_lamports: u64,
+) -> Result<()> {
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+    require!(boost_lamports > 0, LimoError::InvalidTipTransferAmount);
+
+    order.tip_boost_lamports = order
+        .tip_boost_lamports
+        .checked_add(boost_lamports)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    global_config.total_tip_amount = global_config
+        .total_tip_amount
+        .checked_add(boost_lamports)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+
+    Ok(())
+}
+
+pub fn propose_order_transfer(
+    order: &Order,
+    order_key: Pubkey,
+    proposal: &mut OrderTransferProposal,
+    new_maker: Pubkey,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+    require_keys_neq!(new_maker, order.maker, LimoError::InvalidOrderOwner);
+
+    proposal.order = order_key;
+    proposal.pending_new_maker = new_maker;
+    proposal.pending_transfer_timestamp = current_timestamp;
+
+    Ok(())
+}
+
+pub fn accept_order_transfer(
+    order: &mut Order,
+    global_config: &mut GlobalConfig,
+    proposal: &OrderTransferProposal,
+    current_timestamp: u64,
+) -> Result<u64> {
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+    require!(
+        current_timestamp
+            >= proposal
+                .pending_transfer_timestamp
+                .saturating_add(global_config.transfer_finalization_delay_seconds),
+        LimoError::OrderTransferNotReady
+    );
+
+    let previous_maker_tip_amount = withdraw_maker_tip(order, global_config)?;
+    order.maker = proposal.pending_new_maker;
+
+    Ok(previous_maker_tip_amount)
+}
+
+pub fn transfer_order(
+    order: &mut Order,
+    global_config: &mut GlobalConfig,
+    new_maker: Pubkey,
+    current_timestamp: u64,
+) -> Result<u64> {
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+    require_keys_neq!(new_maker, Pubkey::default(), LimoError::InvalidOrderOwner);
+    require_keys_neq!(new_maker, order.maker, LimoError::InvalidOrderOwner);
+
+    let previous_maker_tip_amount = withdraw_maker_tip(order, global_config)?;
+    order.maker = new_maker;
+    order.last_updated_timestamp = current_timestamp;
+
+    Ok(previous_maker_tip_amount)
+}
+
+pub fn withdraw_host_tip(
+    global_config: &mut GlobalConfig,
+    pda_authority_balance: u64,
+) -> Result<u64> {
+    require_gte!(
+        pda_authority_balance,
+        global_config.host_tip_amount,
+        LimoError::InvalidHostTipBalance
+    );
+    let host_tip_amount = global_config.host_tip_amount;
+    global_config.total_tip_amount -= host_tip_amount;
+    global_config.host_tip_amount = 0;
+    Ok(host_tip_amount)
+}
+
+pub fn flash_withdraw_order_input(
+    global_config: &GlobalConfig,
+    order: &mut Order,
+    input_amount: u64,
+    output_amount: u64,
+    current_slot: clock::Slot,
+    current_timestamp: clock::UnixTimestamp,
+    dutch_auction: Option<&DutchAuction>,
+    stop_limit: Option<(&StopLimitTrigger, &OraclePrice)>,
+) -> Result<TakeOrderEffects> {
+    let TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        ..
+    } = take_order_calcs(
+        global_config,
+        order,
+        input_amount,
+        output_amount,
+        current_slot,
+        current_timestamp,
+        dutch_auction,
+        stop_limit,
+    )?;
+
+    require!(
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+
+    order.flash_ix_lock = 1;
+    Ok(TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        boost_to_send_to_taker: 0,
+        referrer_tip_to_send: 0,
+    })
+}
+
+pub fn flash_pay_order_output(
+    global_config: &mut GlobalConfig,
+    order: &mut Order,
+    input_amount: u64,
+    output_amount: u64,
+    tip_amount: u64,
+    current_timestamp: clock::UnixTimestamp,
+    current_slot: clock::Slot,
+    vwap_oracle: Option<&mut VwapOracle>,
+    mint_fee_config: Option<&MintFeeConfig>,
+    has_referrer: bool,
+    dutch_auction: Option<&DutchAuction>,
+    stop_limit: Option<(&StopLimitTrigger, &OraclePrice)>,
+    maker_volume: Option<&mut MakerVolume>,
+    maker_analytics: Option<&mut MakerAnalytics>,
+) -> Result<TakeOrderEffects> {
+    let TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        ..
+    } = take_order_calcs(
+        global_config,
+        order,
+        input_amount,
+        output_amount,
+        current_slot,
+        current_timestamp,
+        dutch_auction,
+        stop_limit,
+    )?;
+
+    require!(
+        order.flash_ix_lock == 1,
+        LimoError::OrderNotWithinFlashOperation
+    );
 
-    update_take_order_accounting_and_tips(
+    let TipCalcs {
+        boost_tip,
+        referrer_tip: referrer_tip_to_send,
+        unfilled_tip_refund,
+        ..
+    } = update_take_order_accounting_and_tips(
         global_config,
         order,
         input_to_send_to_taker,
         output_to_send_to_maker,
         tip_amount,
         current_timestamp,
+        vwap_oracle,
+        mint_fee_config,
+        has_referrer,
+        maker_volume,
+        maker_analytics,
     )?;
 
+    order.flash_ix_lock = 0;
     Ok(TakeOrderEffects {
         input_to_send_to_taker,
         output_to_send_to_maker,
+        boost_to_send_to_taker: boost_tip + unfilled_tip_refund,
+        referrer_tip_to_send,
     })
 }
 
-pub fn update_global_config(
-    global_config: &mut GlobalConfig,
-    mode: UpdateGlobalConfigMode,
-    value: &[u8; UPDATE_GLOBAL_CONFIG_BYTE_SIZE],
-    ts: u64,
-) -> Result<()> {
-    match mode {
-        UpdateGlobalConfigMode::UpdateEmergencyMode
-        | UpdateGlobalConfigMode::UpdateFlashTakeOrderBlocked
-        | UpdateGlobalConfigMode::UpdateBlockNewOrders
-        | UpdateGlobalConfigMode::UpdateBlockOrderTaking
-        | UpdateGlobalConfigMode::UpdateOrderTakingPermissionless => {
-            let value = value[0];
-            update_global_config_flag(global_config, mode, value, ts)?;
-        }
-        UpdateGlobalConfigMode::UpdateHostFeeBps => {
-            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
-            require!(value <= 10000, LimoError::InvalidHostFee);
-            msg!("update_global_config mode={:?} ts={}", mode, ts);
-            msg!("new={} prev={}", value, global_config.host_fee_bps);
-            global_config.host_fee_bps = value;
+pub fn check_min_tip(global_config: &GlobalConfig, tip: u64, is_filled_by_per: bool) -> Result<()> {
+    require!(
+        is_filled_by_per || tip >= global_config.min_tip_amount,
+        LimoError::TipBelowMinimum
+    );
+
+    Ok(())
+}
+
+pub fn take_order_calcs(
+    global_config: &GlobalConfig,
+    order: &Order,
+    input_amount: u64,
+    output_amount: u64,
+    current_slot: clock::Slot,
+    current_timestamp: clock::UnixTimestamp,
+    dutch_auction: Option<&DutchAuction>,
+    stop_limit: Option<(&StopLimitTrigger, &OraclePrice)>,
+) -> Result<TakeOrderEffects> {
+    require!(input_amount > 0, LimoError::OrderInputAmountInvalid);
+
+    require!(
+        order.status == OrderStatus::Active as u8,
+        LimoError::OrderNotActive
+    );
+
+    // Takes never persist expiry, keeper_close_slot_expired_order moves the order to Expired
+    require!(
+        !order.is_expired(
+            current_slot,
+            current_timestamp.try_into().expect("Negative timestamp"),
+        ),
+        LimoError::OrderExpired
+    );
+
+    require!(
+        order.max_fills == 0 || order.number_of_fills < u64::from(order.max_fills),
+        LimoError::MaxFillsReached
+    );
+
+    require!(
+        !global_config.is_order_type_deprecated(order.order_type),
+        LimoError::OrderTypeDeprecated
+    );
+
+    require!(
+        input_amount <= order.remaining_input_amount,
+        LimoError::OrderInputAmountTooLarge
+    );
+
+    if order.order_type == u8::from(OrderType::FillOrKill) {
+        require!(
+            input_amount == order.remaining_input_amount,
+            LimoError::OrderInputAmountTooLarge
+        );
+    }
+
+    let input_to_send_to_taker = input_amount;
+    let minimum_output_to_send_to_maker = minimum_output_for_input(order, input_to_send_to_taker)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+
+    let output_to_send_to_maker = cmp::max(output_amount, minimum_output_to_send_to_maker);
+
+    if output_to_send_to_maker != output_amount {
+        msg!("output_amount: {}", output_amount);
+        msg!(
+            "minimum_output_to_send_to_maker: {}",
+            minimum_output_to_send_to_maker
+        );
+        return err!(LimoError::OrderOutputAmountInvalid);
+    }
+
+    if order.order_type == u8::from(OrderType::DutchAuction) {
+        let dutch_auction = dutch_auction.ok_or(LimoError::InvalidAccount)?;
+        let auction_output_amount = dutch_auction_output_amount(
+            dutch_auction,
+            current_timestamp.try_into().expect("Negative timestamp"),
+        );
+        let minimum_auction_output = (u128::from(input_to_send_to_taker)
+            * u128::from(auction_output_amount))
+        .div_ceil(u128::from(order.initial_input_amount));
+
+        if u128::from(output_to_send_to_maker) < minimum_auction_output {
+            msg!("output_amount: {}", output_amount);
+            msg!("minimum_auction_output: {}", minimum_auction_output);
+            return err!(LimoError::DutchAuctionPriceNotMet);
         }
-        UpdateGlobalConfigMode::UpdateOrderCloseDelaySeconds => {
-            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
-            msg!("update_global_config mode={:?} ts={}", mode, ts);
+    }
+
+    if order.order_type == u8::from(OrderType::StopLimit) {
+        let (stop_limit_trigger, oracle_price) = stop_limit.ok_or(LimoError::InvalidAccount)?;
+        if !is_oracle_price_at_or_above(
+            oracle_price,
+            stop_limit_trigger.trigger_price_numerator,
+            stop_limit_trigger.trigger_price_denominator,
+        )? {
             msg!(
-                "new={} prev={}",
-                value,
-                global_config.order_close_delay_seconds
+                "oracle_price: {} exponent: {}",
+                oracle_price.price,
+                oracle_price.exponent
             );
-            global_config.order_close_delay_seconds = value;
-        }
-        UpdateGlobalConfigMode::UpdateAdminAuthorityCached => {
-            let value = Pubkey::new_from_array(value[0..32].try_into().unwrap());
-            update_global_config_pubkey(global_config, mode, value, ts)?
+            msg!(
+                "trigger_price: {}/{}",
+                stop_limit_trigger.trigger_price_numerator,
+                stop_limit_trigger.trigger_price_denominator
+            );
+            return err!(LimoError::StopPriceNotMet);
         }
-        UpdateGlobalConfigMode::UpdateTxnFeeCost => {
-            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
-            msg!("update_global_config mode={:?} ts={}", mode, ts);
-            msg!("new={} prev={}", value, global_config.txn_fee_cost);
-            global_config.txn_fee_cost = value;
+    }
+
+    msg!("input_to_send_to_taker: {}", input_to_send_to_taker);
+    msg!("output_to_send_to_maker: {}", output_to_send_to_maker);
+
+    Ok(TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        boost_to_send_to_taker: 0,
+        referrer_tip_to_send: 0,
+    })
+}
+
+pub fn initialize_dutch_auction(
+    order: &Order,
+    order_key: Pubkey,
+    dutch_auction: &mut DutchAuction,
+    start_output_amount: u64,
+    duration_seconds: u64,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(
+        order.order_type == u8::from(OrderType::DutchAuction),
+        LimoError::OrderTypeInvalid
+    );
+    require!(
+        order.status == OrderStatus::Active as u8 && order.number_of_fills == 0,
+        LimoError::OrderNotActive
+    );
+    require!(
+        start_output_amount >= order.expected_output_amount,
+        LimoError::OrderOutputAmountInvalid
+    );
+    require!(duration_seconds > 0, LimoError::InvalidConfigOption);
+
+    dutch_auction.order = order_key;
+    dutch_auction.start_output_amount = start_output_amount;
+    dutch_auction.end_output_amount = order.expected_output_amount;
+    dutch_auction.start_timestamp = current_timestamp;
+    dutch_auction.end_timestamp = current_timestamp
+        .checked_add(duration_seconds)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+
+    Ok(())
+}
+
+pub fn initialize_stop_limit_trigger(
+    order: &Order,
+    order_key: Pubkey,
+    stop_limit_trigger: &mut StopLimitTrigger,
+    price_feed: Pubkey,
+    trigger_price_numerator: u64,
+    trigger_price_denominator: u64,
+) -> Result<()> {
+    require!(
+        order.order_type == u8::from(OrderType::StopLimit),
+        LimoError::OrderTypeInvalid
+    );
+    require!(
+        order.status == OrderStatus::Active as u8 && order.number_of_fills == 0,
+        LimoError::OrderNotActive
+    );
+    require!(
+        trigger_price_numerator > 0 && trigger_price_denominator > 0,
+        LimoError::InvalidParameterType
+    );
+
+    stop_limit_trigger.order = order_key;
+    stop_limit_trigger.price_feed = price_feed;
+    stop_limit_trigger.trigger_price_numerator = trigger_price_numerator;
+    stop_limit_trigger.trigger_price_denominator = trigger_price_denominator;
+
+    Ok(())
+}
+
+pub fn dutch_auction_output_amount(dutch_auction: &DutchAuction, current_timestamp: u64) -> u64 {
+    if current_timestamp >= dutch_auction.end_timestamp {
+        return dutch_auction.end_output_amount;
+    }
+
+    let elapsed = current_timestamp.saturating_sub(dutch_auction.start_timestamp);
+    let duration = dutch_auction.end_timestamp - dutch_auction.start_timestamp;
+    let decay = Fraction::from(dutch_auction.start_output_amount - dutch_auction.end_output_amount)
+        .full_mul_int_ratio(elapsed, duration);
+
+    dutch_auction.start_output_amount - decay.to_floor::<u64>()
+}
+
+fn minimum_output_for_input(order: &Order, input_amount: u64) -> Option<u64> {
+    if order.initial_input_amount == 0 {
+        return None;
+    }
+
+    let minimum_output_u128 = (u128::from(input_amount) * u128::from(order.expected_output_amount))
+        .div_ceil(u128::from(order.initial_input_amount));
+
+    u64::try_from(minimum_output_u128).ok()
+}
+
+pub fn is_order_active(order: &Order) -> bool {
+    order.status == OrderStatus::Active as u8
+        && order.flash_ix_lock == 0
+        && order.remaining_input_amount > 0
+}
+
+pub fn is_order_fillable_by(order: &Order, taker: &Pubkey) -> bool {
+    is_order_active(order) && is_counterparty_matching(&order.counterparty, &[], taker)
+}
+
+pub fn order_age_seconds(order: &Order, current_timestamp: u64) -> u64 {
+    current_timestamp.saturating_sub(order.created_timestamp)
+}
+
+pub fn slots_to_expiry(order: &Order, current_slot: clock::Slot) -> Option<u64> {
+    if order.expiry_slot == 0 {
+        return None;
+    }
+
+    Some(order.expiry_slot.saturating_sub(current_slot))
+}
+
+pub fn time_to_expiry_seconds(order: &Order, current_timestamp: u64) -> Option<u64> {
+    if order.expiry_timestamp == 0 {
+        return None;
+    }
+
+    Some(order.expiry_timestamp.saturating_sub(current_timestamp))
+}
+
+pub fn order_fill_percentage_bps(order: &Order) -> u16 {
+    if order.initial_input_amount == 0 {
+        return 0;
+    }
+
+    let filled_input_amount = order
+        .initial_input_amount
+        .saturating_sub(order.remaining_input_amount);
+    let fill_bps = u128::from(filled_input_amount) * u128::from(FULL_BPS)
+        / u128::from(order.initial_input_amount);
+
+    u16::try_from(fill_bps).unwrap_or(u16::MAX)
+}
+
+pub fn is_order_above_min_fill(order: &Order, input_amount: u64) -> bool {
+    compute_fill_output(input_amount, order).is_some_and(|output| output > 0)
+}
+
+pub fn filter_orders_by_tag<'a>(
+    orders: &'a [(Pubkey, Order)],
+    tag_mask: &[u8; 16],
+) -> Vec<(Pubkey, &'a Order)> {
+    orders
+        .iter()
+        .filter(|(_, order)| {
+            order
+                .tags
+                .iter()
+                .zip(tag_mask.iter())
+                .all(|(tag, mask)| tag & mask == *mask)
+        })
+        .map(|(key, order)| (*key, order))
+        .collect()
+}
+
+pub fn compute_fill_output(input_amount: u64, order: &Order) -> Option<u64> {
+    if input_amount == 0
+        || order.status != OrderStatus::Active as u8
+        || input_amount > order.remaining_input_amount
+    {
+        return None;
+    }
+
+    minimum_output_for_input(order, input_amount)
+}
+
+pub fn take_order(
+    global_config: &mut GlobalConfig,
+    order: &mut Order,
+    input_amount: u64,
+    tip_amount: u64,
+    current_timestamp: clock::UnixTimestamp,
+    current_slot: clock::Slot,
+    output_amount: u64,
+    vwap_oracle: Option<&mut VwapOracle>,
+    mint_fee_config: Option<&MintFeeConfig>,
+    has_referrer: bool,
+    dutch_auction: Option<&DutchAuction>,
+    stop_limit: Option<(&StopLimitTrigger, &OraclePrice)>,
+    maker_volume: Option<&mut MakerVolume>,
+    maker_analytics: Option<&mut MakerAnalytics>,
+) -> Result<TakeOrderEffects> {
+    require!(
+        order.flash_ix_lock == 0,
+        LimoError::OrderWithinFlashOperation
+    );
+
+    let TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        ..
+    } = take_order_calcs(
+        global_config,
+        order,
+        input_amount,
+        output_amount,
+        current_slot,
+        current_timestamp,
+        dutch_auction,
+        stop_limit,
+    )?;
+
+    let TipCalcs {
+        boost_tip,
+        referrer_tip: referrer_tip_to_send,
+        unfilled_tip_refund,
+        ..
+    } = update_take_order_accounting_and_tips(
+        global_config,
+        order,
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        tip_amount,
+        current_timestamp,
+        vwap_oracle,
+        mint_fee_config,
+        has_referrer,
+        maker_volume,
+        maker_analytics,
+    )?;
+
+    Ok(TakeOrderEffects {
+        input_to_send_to_taker,
+        output_to_send_to_maker,
+        boost_to_send_to_taker: boost_tip + unfilled_tip_refund,
+        referrer_tip_to_send,
+    })
+}
+
+pub fn update_global_config(
+    global_config: &mut GlobalConfig,
+    mode: UpdateGlobalConfigMode,
+    value: &[u8; UPDATE_GLOBAL_CONFIG_BYTE_SIZE],
+    ts: u64,
+) -> Result<()> {
+    match mode {
+        UpdateGlobalConfigMode::UpdateEmergencyMode
+        | UpdateGlobalConfigMode::UpdateMultisigEnabled
+        | UpdateGlobalConfigMode::UpdateHostFeeRoundingMode
+        | UpdateGlobalConfigMode::UpdateFlashTakeOrderBlocked
+        | UpdateGlobalConfigMode::UpdateBlockNewOrders
+        | UpdateGlobalConfigMode::UpdateBlockOrderTaking
+        | UpdateGlobalConfigMode::UpdateOrderTakingPermissionless
+        | UpdateGlobalConfigMode::UpdateExpressRelayDisabled => {
+            let value = value[0];
+            update_global_config_flag(global_config, mode, value, ts)?;
+        }
+        UpdateGlobalConfigMode::UpdateHostFeeBps => {
+            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
+            require!(value <= 10000, LimoError::InvalidHostFee);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.host_fee_bps);
+            global_config.host_fee_bps = value;
+        }
+        UpdateGlobalConfigMode::UpdateOrderCloseDelaySeconds => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.order_close_delay_seconds
+            );
+            global_config.order_close_delay_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateAdminAuthorityCached
+        | UpdateGlobalConfigMode::UpdateFeeTierNftMint
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook0
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook1
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook2
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook3
+        | UpdateGlobalConfigMode::UpdateFeeCollector => {
+            let value = Pubkey::new_from_array(value[0..32].try_into().unwrap());
+            update_global_config_pubkey(global_config, mode, value, ts)?
+        }
+        UpdateGlobalConfigMode::UpdateFeeTierPremiumHostFeeBps => {
+            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
+            require!(value <= 10000, LimoError::InvalidHostFee);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.fee_tier_premium_host_fee_bps
+            );
+            global_config.fee_tier_premium_host_fee_bps = value;
+        }
+        UpdateGlobalConfigMode::UpdateReferrerFeeBps => {
+            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
+            require!(value <= 10000, LimoError::InvalidReferrerFee);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.referrer_fee_bps);
+            global_config.referrer_fee_bps = value;
+        }
+        UpdateGlobalConfigMode::UpdateOraclePriceToleranceBps => {
+            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
+            require!(value <= 10000, LimoError::InvalidOraclePriceTolerance);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.oracle_price_tolerance_bps
+            );
+            global_config.oracle_price_tolerance_bps = value;
+        }
+        UpdateGlobalConfigMode::UpdateMaxOrdersPerMaker => {
+            let value = u32::from_le_bytes(value[0..4].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.max_orders_per_maker);
+            global_config.max_orders_per_maker = value;
+        }
+        UpdateGlobalConfigMode::UpdateMinTipAmount => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.min_tip_amount);
+            global_config.min_tip_amount = value;
+        }
+        UpdateGlobalConfigMode::UpdateMinOrderInputAmount => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.min_order_input_amount
+            );
+            global_config.min_order_input_amount = value;
+        }
+        UpdateGlobalConfigMode::UpdateMaxOrderInputAmount => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.max_order_input_amount
+            );
+            global_config.max_order_input_amount = value;
+        }
+        UpdateGlobalConfigMode::UpdateBaseFeeLamports => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.base_fee_lamports);
+            global_config.base_fee_lamports = value;
+        }
+        UpdateGlobalConfigMode::UpdateEpochSeconds => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.epoch_seconds);
+            global_config.epoch_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateVolumeRebateBps => {
+            let value = u16::from_le_bytes(value[0..2].try_into().unwrap());
+            require!(value <= 10000, LimoError::InvalidHostFee);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.volume_rebate_bps);
+            global_config.volume_rebate_bps = value;
+        }
+        UpdateGlobalConfigMode::UpdateProtocolVersion => {
+            let value = u32::from_le_bytes(value[0..4].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.protocol_version);
+            global_config.protocol_version = value;
+        }
+        UpdateGlobalConfigMode::UpdateProposalExpirySeconds => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.proposal_expiry_seconds
+            );
+            global_config.proposal_expiry_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateMultisigThreshold => {
+            msg!("Multisig threshold is stored on the multisig admin account");
+            return err!(LimoError::InvalidConfigOption);
+        }
+        UpdateGlobalConfigMode::UpdateTxnFeeCost => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.txn_fee_cost);
+            global_config.txn_fee_cost = value;
+        }
+        UpdateGlobalConfigMode::UpdateAtaCreationCost => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!("new={} prev={}", value, global_config.ata_creation_cost);
+            global_config.ata_creation_cost = value;
+        }
+        UpdateGlobalConfigMode::UpdateMakerCloseCooldown => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.maker_close_cooldown_seconds
+            );
+            global_config.maker_close_cooldown_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateDeprecatedOrderTypes => {
+            let value = u32::from_le_bytes(value[0..4].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={:#034b} prev={:#034b}",
+                value,
+                global_config.deprecated_order_types
+            );
+            global_config.deprecated_order_types = value;
+        }
+        UpdateGlobalConfigMode::UpdateTransferFinalizationDelaySeconds => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.transfer_finalization_delay_seconds
+            );
+            global_config.transfer_finalization_delay_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateMinOrderLifetimeSeconds => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.min_order_lifetime_seconds
+            );
+            global_config.min_order_lifetime_seconds = value;
+        }
+        UpdateGlobalConfigMode::UpdateMaxFlashIntermediaryIxs => {
+            let value = value[0];
+            require!(value > 0, LimoError::InvalidConfigOption);
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.max_flash_intermediary_ixs
+            );
+            global_config.max_flash_intermediary_ixs = value;
+        }
+        UpdateGlobalConfigMode::UpdateOrderUpdateDelaySlots => {
+            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
+            msg!("update_global_config mode={:?} ts={}", mode, ts);
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.order_update_delay_slots
+            );
+            global_config.order_update_delay_slots = value;
+        }
+    }
+    Ok(())
+}
+
+pub fn initialize_vwap_oracle(
+    vwap_oracle: &mut VwapOracle,
+    global_config: Pubkey,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    window_seconds: u64,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(window_seconds > 0, LimoError::InvalidConfigOption);
+
+    vwap_oracle.global_config = global_config;
+    vwap_oracle.input_mint = input_mint;
+    vwap_oracle.output_mint = output_mint;
+    vwap_oracle.cumulative_input_volume = 0;
+    vwap_oracle.cumulative_output_volume = 0;
+    vwap_oracle.last_update_timestamp = current_timestamp;
+    vwap_oracle.window_seconds = window_seconds;
+    vwap_oracle.vwap_numerator = 0;
+    vwap_oracle.vwap_denominator = 0;
+
+    Ok(())
+}
+
+pub fn initialize_order_book_snapshot(
+    order_book_snapshot: &mut OrderBookSnapshot,
+    global_config: Pubkey,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    current_slot: u64,
+) {
+    order_book_snapshot.global_config = global_config;
+    order_book_snapshot.input_mint = input_mint;
+    order_book_snapshot.output_mint = output_mint;
+    reset_order_book_snapshot(order_book_snapshot, current_slot);
+}
+
+pub fn reset_order_book_snapshot(order_book_snapshot: &mut OrderBookSnapshot, current_slot: u64) {
+    order_book_snapshot.set_total_remaining_input(0);
+    order_book_snapshot.set_total_expected_output(0);
+    order_book_snapshot.active_order_count = 0;
+    order_book_snapshot.last_updated_slot = current_slot;
+}
+
+pub fn add_order_to_order_book_snapshot(
+    order_book_snapshot: &mut OrderBookSnapshot,
+    order: &Order,
+    current_slot: u64,
+) {
+    order_book_snapshot.set_total_remaining_input(
+        order_book_snapshot
+            .total_remaining_input()
+            .saturating_add(order.remaining_input_amount.into()),
+    );
+    order_book_snapshot.set_total_expected_output(
+        order_book_snapshot
+            .total_expected_output()
+            .saturating_add(order.expected_output_amount.into()),
+    );
+    order_book_snapshot.active_order_count =
+        order_book_snapshot.active_order_count.saturating_add(1);
+    order_book_snapshot.last_updated_slot = current_slot;
+}
+
+pub fn remove_order_from_order_book_snapshot(
+    order_book_snapshot: &mut OrderBookSnapshot,
+    order: &Order,
+    current_slot: u64,
+) {
+    order_book_snapshot.set_total_remaining_input(
+        order_book_snapshot
+            .total_remaining_input()
+            .saturating_sub(order.remaining_input_amount.into()),
+    );
+    order_book_snapshot.set_total_expected_output(
+        order_book_snapshot
+            .total_expected_output()
+            .saturating_sub(order.expected_output_amount.into()),
+    );
+    order_book_snapshot.active_order_count =
+        order_book_snapshot.active_order_count.saturating_sub(1);
+    order_book_snapshot.last_updated_slot = current_slot;
+}
+
+pub fn check_order_book_snapshot_for_order(
+    order_book_snapshot: &OrderBookSnapshot,
+    order: &Order,
+) -> Result<()> {
+    require!(
+        order.global_config == order_book_snapshot.global_config
+            && order.input_mint == order_book_snapshot.input_mint
+            && order.output_mint == order_book_snapshot.output_mint,
+        LimoError::InvalidAccount
+    );
+
+    Ok(())
+}
+
+/// Swaps the contribution of `order_before` for that of `order`, only active
+/// orders are counted in the snapshot
+pub fn update_order_book_snapshot(
+    order_book_snapshot: &mut OrderBookSnapshot,
+    order_before: &Order,
+    order: &Order,
+    current_slot: u64,
+) -> Result<()> {
+    check_order_book_snapshot_for_order(order_book_snapshot, order)?;
+
+    if order_before.status == OrderStatus::Active as u8 {
+        remove_order_from_order_book_snapshot(order_book_snapshot, order_before, current_slot);
+    }
+    if order.status == OrderStatus::Active as u8 {
+        add_order_to_order_book_snapshot(order_book_snapshot, order, current_slot);
+    }
+
+    Ok(())
+}
+
+pub fn current_volume_epoch(global_config: &GlobalConfig, current_timestamp: i64) -> Result<u64> {
+    require!(
+        global_config.epoch_seconds > 0,
+        LimoError::InvalidConfigOption
+    );
+    let current_timestamp =
+        u64::try_from(current_timestamp).map_err(|_| dbg_msg!(LimoError::MathOverflow))?;
+    Ok(current_timestamp / global_config.epoch_seconds)
+}
+
+pub fn initialize_maker_volume(
+    maker_volume: &mut MakerVolume,
+    global_config: Pubkey,
+    maker: Pubkey,
+    epoch: u64,
+) {
+    maker_volume.global_config = global_config;
+    maker_volume.maker = maker;
+    maker_volume.epoch = epoch;
+    maker_volume.set_input_volume(0);
+    maker_volume.set_output_volume(0);
+    maker_volume.tip_paid = 0;
+    maker_volume.fill_count = 0;
+    maker_volume.rebate_claimed = 0;
+}
+
+pub fn initialize_maker_analytics(
+    maker_analytics: &mut MakerAnalytics,
+    global_config: Pubkey,
+    maker: Pubkey,
+) {
+    maker_analytics.global_config = global_config;
+    maker_analytics.maker = maker;
+    maker_analytics.total_orders_created = 0;
+    maker_analytics.total_orders_filled = 0;
+    maker_analytics.total_orders_cancelled = 0;
+    maker_analytics.set_total_input_volume(0);
+    maker_analytics.set_total_output_volume(0);
+    maker_analytics.total_tips_earned = 0;
+    maker_analytics.first_order_ts = 0;
+    maker_analytics.last_order_ts = 0;
+}
+
+pub fn record_maker_analytics_order_created(
+    maker_analytics: &mut MakerAnalytics,
+    current_timestamp: u64,
+) {
+    if maker_analytics.total_orders_created == 0 {
+        maker_analytics.first_order_ts = current_timestamp;
+    }
+    maker_analytics.total_orders_created = maker_analytics.total_orders_created.saturating_add(1);
+    maker_analytics.last_order_ts = current_timestamp;
+}
+
+pub fn record_order_closed(
+    order: &Order,
+    was_active: bool,
+    was_filled: bool,
+    maker_analytics: Option<&mut MakerAnalytics>,
+    order_book_snapshot: &mut OrderBookSnapshot,
+    current_slot: u64,
+) -> Result<()> {
+    check_order_book_snapshot_for_order(order_book_snapshot, order)?;
+
+    if let Some(maker_analytics) = maker_analytics {
+        record_maker_analytics_order_closed(maker_analytics, was_filled);
+    }
+
+    if was_active {
+        remove_order_from_order_book_snapshot(order_book_snapshot, order, current_slot);
+    }
+
+    Ok(())
+}
+
+pub fn record_maker_analytics_order_closed(maker_analytics: &mut MakerAnalytics, was_filled: bool) {
+    if was_filled {
+        maker_analytics.total_orders_filled = maker_analytics.total_orders_filled.saturating_add(1);
+    } else {
+        maker_analytics.total_orders_cancelled =
+            maker_analytics.total_orders_cancelled.saturating_add(1);
+    }
+}
+
+fn record_maker_analytics_fill(
+    order: &Order,
+    maker_analytics: &mut MakerAnalytics,
+    input_amount: u64,
+    output_amount: u64,
+    maker_tip: u64,
+) -> Result<()> {
+    require!(
+        maker_analytics.global_config == order.global_config
+            && maker_analytics.maker == order.maker,
+        LimoError::InvalidMakerAnalytics
+    );
+
+    maker_analytics.set_total_input_volume(
+        maker_analytics
+            .total_input_volume()
+            .saturating_add(input_amount.into()),
+    );
+    maker_analytics.set_total_output_volume(
+        maker_analytics
+            .total_output_volume()
+            .saturating_add(output_amount.into()),
+    );
+    maker_analytics.total_tips_earned = maker_analytics.total_tips_earned.saturating_add(maker_tip);
+
+    Ok(())
+}
+
+fn record_maker_volume(
+    global_config: &GlobalConfig,
+    order: &Order,
+    maker_volume: &mut MakerVolume,
+    input_amount: u64,
+    output_amount: u64,
+    host_tip: u64,
+    current_timestamp: i64,
+) -> Result<()> {
+    require!(
+        maker_volume.global_config == order.global_config
+            && maker_volume.maker == order.maker
+            && maker_volume.epoch == current_volume_epoch(global_config, current_timestamp)?,
+        LimoError::InvalidMakerVolume
+    );
+
+    maker_volume.set_input_volume(
+        maker_volume
+            .input_volume()
+            .saturating_add(input_amount.into()),
+    );
+    maker_volume.set_output_volume(
+        maker_volume
+            .output_volume()
+            .saturating_add(output_amount.into()),
+    );
+    maker_volume.tip_paid = maker_volume.tip_paid.saturating_add(host_tip);
+    maker_volume.fill_count = maker_volume.fill_count.saturating_add(1);
+
+    Ok(())
+}
+
+pub fn claim_volume_rebate(
+    global_config: &mut GlobalConfig,
+    maker_volume: &mut MakerVolume,
+    current_timestamp: i64,
+    pda_authority_balance: u64,
+) -> Result<u64> {
+    require!(
+        maker_volume.epoch < current_volume_epoch(global_config, current_timestamp)?
+            && maker_volume.rebate_claimed == 0,
+        LimoError::VolumeRebateNotClaimable
+    );
+
+    let rebate = u64::try_from(
+        u128::from(maker_volume.tip_paid) * u128::from(global_config.volume_rebate_bps)
+            / u128::from(FULL_BPS),
+    )
+    .map_err(|_| dbg_msg!(LimoError::MathOverflow))?;
+    let rebate = cmp::min(rebate, global_config.host_tip_amount);
+    require!(rebate > 0, LimoError::VolumeRebateNotClaimable);
+    require_gte!(
+        pda_authority_balance,
+        global_config.host_tip_amount,
+        LimoError::InvalidHostTipBalance
+    );
+
+    global_config.host_tip_amount -= rebate;
+    global_config.total_tip_amount -= rebate;
+    maker_volume.rebate_claimed = rebate;
+
+    Ok(rebate)
+}
+
+pub fn set_multisig_admin_signers(
+    multisig_admin: &mut MultiSigAdmin,
+    signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+    threshold: u8,
+) -> Result<()> {
+    for (idx, signer) in signers.iter().enumerate() {
+        require!(
+            *signer == Pubkey::default() || !signers[..idx].contains(signer),
+            LimoError::InvalidMultisigSigner
+        );
+    }
+
+    multisig_admin.signers = signers;
+    update_multisig_threshold(multisig_admin, threshold)
+}
+
+/// Enabling the multisig locks out the single admin, so it needs a multisig
+/// admin that can approve proposals on its own.
+pub fn check_multisig_admin_can_take_over(multisig_admin: Option<&MultiSigAdmin>) -> Result<()> {
+    let multisig_admin = multisig_admin.ok_or_else(|| error!(LimoError::MultisigAdminRequired))?;
+    require!(
+        multisig_admin.threshold > 0,
+        LimoError::InvalidMultisigThreshold
+    );
+    Ok(())
+}
+
+pub fn update_multisig_threshold(multisig_admin: &mut MultiSigAdmin, threshold: u8) -> Result<()> {
+    let signer_count = multisig_admin
+        .signers
+        .iter()
+        .filter(|signer| **signer != Pubkey::default())
+        .count();
+    require!(
+        threshold > 0 && usize::from(threshold) <= signer_count,
+        LimoError::InvalidMultisigThreshold
+    );
+
+    msg!(
+        "update_multisig_threshold new={} prev={}",
+        threshold,
+        multisig_admin.threshold
+    );
+    multisig_admin.threshold = threshold;
+
+    Ok(())
+}
+
+pub fn multisig_signer_index(multisig_admin: &MultiSigAdmin, signer: &Pubkey) -> Result<usize> {
+    require_keys_neq!(*signer, Pubkey::default(), LimoError::InvalidMultisigSigner);
+    multisig_admin
+        .signers
+        .iter()
+        .position(|s| s == signer)
+        .ok_or_else(|| error!(LimoError::InvalidMultisigSigner))
+}
+
+pub fn is_proposal_expired(
+    global_config: &GlobalConfig,
+    proposal: &Proposal,
+    current_timestamp: u64,
+) -> bool {
+    global_config.proposal_expiry_seconds > 0
+        && current_timestamp
+            > proposal
+                .created_timestamp
+                .saturating_add(global_config.proposal_expiry_seconds)
+}
+
+pub fn propose_global_config_update(
+    multisig_admin: &mut MultiSigAdmin,
+    proposal: &mut Proposal,
+    multisig_admin_key: Pubkey,
+    proposer: Pubkey,
+    mode: u16,
+    value: [u8; UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE],
+    current_timestamp: u64,
+) -> Result<()> {
+    UpdateGlobalConfigMode::try_from(mode).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let signer_index = multisig_signer_index(multisig_admin, &proposer)?;
+
+    proposal.multisig_admin = multisig_admin_key;
+    proposal.proposer = proposer;
+    proposal.nonce = multisig_admin.nonce;
+    proposal.mode = mode;
+    proposal.value = value;
+    proposal.approvals = 1 << signer_index;
+    proposal.created_timestamp = current_timestamp;
+
+    multisig_admin.nonce = multisig_admin
+        .nonce
+        .checked_add(1)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+
+    Ok(())
+}
+
+pub fn approve_proposal(
+    global_config: &GlobalConfig,
+    multisig_admin: &MultiSigAdmin,
+    proposal: &mut Proposal,
+    signer: &Pubkey,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(
+        !is_proposal_expired(global_config, proposal, current_timestamp),
+        LimoError::ProposalExpired
+    );
+    let signer_index = multisig_signer_index(multisig_admin, signer)?;
+
+    proposal.approvals |= 1 << signer_index;
+
+    Ok(())
+}
+
+pub fn execute_proposal(
+    global_config: &mut GlobalConfig,
+    multisig_admin: &mut MultiSigAdmin,
+    proposal: &Proposal,
+    current_timestamp: u64,
+) -> Result<()> {
+    require!(
+        !is_proposal_expired(global_config, proposal, current_timestamp),
+        LimoError::ProposalExpired
+    );
+    require!(
+        proposal.approvals.count_ones() >= u32::from(multisig_admin.threshold),
+        LimoError::ProposalThresholdNotMet
+    );
+
+    let mode = UpdateGlobalConfigMode::try_from(proposal.mode)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let mut value = [0; UPDATE_GLOBAL_CONFIG_BYTE_SIZE];
+    value[..UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE].copy_from_slice(&proposal.value);
+
+    if mode == UpdateGlobalConfigMode::UpdateMultisigThreshold {
+        update_multisig_threshold(multisig_admin, value[0])
+    } else {
+        update_global_config(global_config, mode, &value, current_timestamp)
+    }
+}
+
+pub fn add_vault_escrow(vault_escrow: &mut VaultEscrow, amount: u64) -> Result<()> {
+    vault_escrow.outstanding_amount = vault_escrow
+        .outstanding_amount
+        .checked_add(amount)
+        .ok_or_else(|| dbg_msg!(LimoError::VaultDustCalculationOverflow))?;
+    Ok(())
+}
+
+pub fn release_vault_escrow(vault_escrow: &mut VaultEscrow, amount: u64) -> Result<()> {
+    vault_escrow.outstanding_amount = vault_escrow
+        .outstanding_amount
+        .checked_sub(amount)
+        .ok_or_else(|| dbg_msg!(LimoError::VaultDustCalculationOverflow))?;
+    Ok(())
+}
+
+pub fn compute_vault_dust(vault_amount: u64, vault_escrow: &VaultEscrow) -> u64 {
+    vault_amount.saturating_sub(vault_escrow.outstanding_amount)
+}
+
+pub fn initialize_mint_fee_config(
+    mint_fee_config: &mut MintFeeConfig,
+    global_config: Pubkey,
+    mint: Pubkey,
+    host_fee_bps: u16,
+    enabled: u8,
+) -> Result<()> {
+    mint_fee_config.global_config = global_config;
+    mint_fee_config.mint = mint;
+    update_mint_fee_config(mint_fee_config, host_fee_bps, enabled)
+}
+
+pub fn update_mint_fee_config(
+    mint_fee_config: &mut MintFeeConfig,
+    host_fee_bps: u16,
+    enabled: u8,
+) -> Result<()> {
+    require!(host_fee_bps <= 10000, LimoError::InvalidHostFee);
+    require!(enabled == 0 || enabled == 1, LimoError::InvalidFlag);
+
+    msg!(
+        "mint_fee_config host_fee_bps new={} prev={}",
+        host_fee_bps,
+        mint_fee_config.host_fee_bps
+    );
+    msg!(
+        "mint_fee_config enabled new={} prev={}",
+        enabled,
+        mint_fee_config.enabled
+    );
+
+    mint_fee_config.host_fee_bps = host_fee_bps;
+    mint_fee_config.enabled = enabled;
+
+    Ok(())
+}
+
+pub fn update_vwap_oracle(
+    vwap_oracle: &mut VwapOracle,
+    input_amount: u64,
+    output_amount: u64,
+    current_timestamp: u64,
+) {
+    if current_timestamp.saturating_sub(vwap_oracle.last_update_timestamp)
+        > vwap_oracle.window_seconds
+    {
+        vwap_oracle.cumulative_input_volume = 0;
+        vwap_oracle.cumulative_output_volume = 0;
+    }
+
+    match (
+        vwap_oracle
+            .cumulative_input_volume
+            .checked_add(input_amount),
+        vwap_oracle
+            .cumulative_output_volume
+            .checked_add(output_amount),
+    ) {
+        (Some(cumulative_input_volume), Some(cumulative_output_volume)) => {
+            vwap_oracle.cumulative_input_volume = cumulative_input_volume;
+            vwap_oracle.cumulative_output_volume = cumulative_output_volume;
         }
-        UpdateGlobalConfigMode::UpdateAtaCreationCost => {
-            let value = u64::from_le_bytes(value[0..8].try_into().unwrap());
-            msg!("update_global_config mode={:?} ts={}", mode, ts);
-            msg!("new={} prev={}", value, global_config.ata_creation_cost);
-            global_config.ata_creation_cost = value;
+        _ => {
+            vwap_oracle.cumulative_input_volume = input_amount;
+            vwap_oracle.cumulative_output_volume = output_amount;
         }
     }
-    Ok(())
+
+    vwap_oracle.vwap_numerator = vwap_oracle.cumulative_output_volume;
+    vwap_oracle.vwap_denominator = vwap_oracle.cumulative_input_volume;
+    vwap_oracle.last_update_timestamp = current_timestamp;
+}
+
+/// Input-weighted average fill price as `(output, input)`. Weighting each
+/// fill's price by its input amount reduces to total output over total input,
+/// so no per-fill state needs to be stored on the order
+pub fn compute_vwap_fill_price(order: &Order) -> Option<(u64, u64)> {
+    let filled_input_amount = order
+        .initial_input_amount
+        .checked_sub(order.remaining_input_amount)?;
+
+    if filled_input_amount == 0 {
+        return None;
+    }
+
+    Some((order.filled_output_amount, filled_input_amount))
+}
+
+pub fn compute_fill_receipt_hash(
+    order_key: &Pubkey,
+    fill_index: u64,
+    input_sent: u64,
+    output_sent: u64,
+    timestamp: u64,
+) -> [u8; 32] {
+    hashv(&[
+        order_key.as_ref(),
+        &fill_index.to_le_bytes(),
+        &input_sent.to_le_bytes(),
+        &output_sent.to_le_bytes(),
+        &timestamp.to_le_bytes(),
+    ])
+    .to_bytes()
 }
 
 pub fn validate_pda_authority_balance_and_update_accounting(
@@ -375,7 +1978,28 @@ fn update_take_order_accounting_and_tips(
     output_to_send_to_maker: u64,
     tip_amount: u64,
     current_timestamp: i64,
-) -> Result<()> {
+    vwap_oracle: Option<&mut VwapOracle>,
+    mint_fee_config: Option<&MintFeeConfig>,
+    has_referrer: bool,
+    maker_volume: Option<&mut MakerVolume>,
+    maker_analytics: Option<&mut MakerAnalytics>,
+) -> Result<TipCalcs> {
+    let mut calcs = tip_calcs(
+        global_config,
+        order,
+        tip_amount,
+        input_to_send_to_taker,
+        mint_fee_config,
+        has_referrer,
+    )?;
+    let TipCalcs {
+        host_tip,
+        maker_tip,
+        boost_tip,
+        referrer_tip,
+        ..
+    } = calcs;
+
     order.remaining_input_amount = order
         .remaining_input_amount
         .checked_sub(input_to_send_to_taker)
@@ -386,11 +2010,6 @@ fn update_take_order_accounting_and_tips(
         .checked_add(output_to_send_to_maker)
         .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
 
-    let TipCalcs {
-        host_tip,
-        maker_tip,
-    } = tip_calcs(global_config, tip_amount)?;
-
     global_config.host_tip_amount = global_config
         .host_tip_amount
         .checked_add(host_tip)
@@ -403,31 +2022,157 @@ fn update_take_order_accounting_and_tips(
 
     global_config.total_tip_amount = global_config
         .total_tip_amount
-        .checked_add(tip_amount)
+        .checked_add(tip_amount - referrer_tip)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+
+    order.tip_boost_lamports = order
+        .tip_boost_lamports
+        .checked_sub(boost_tip)
+        .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+    global_config.total_tip_amount = global_config
+        .total_tip_amount
+        .checked_sub(boost_tip)
         .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
 
+    global_config.total_volume_input = global_config
+        .total_volume_input
+        .saturating_add(input_to_send_to_taker);
+    global_config.total_volume_output = global_config
+        .total_volume_output
+        .saturating_add(output_to_send_to_maker);
+
     order.number_of_fills += 1;
 
     if order.remaining_input_amount == 0
         && order.filled_output_amount >= order.expected_output_amount
     {
         order.status = OrderStatus::Filled as u8;
+    } else if order.order_type == u8::from(OrderType::ImmediateOrCancel)
+        && order.remaining_input_amount > 0
+    {
+        let unfilled_tip_refund = u64::try_from(
+            u128::from(maker_tip) * u128::from(order.remaining_input_amount)
+                / (u128::from(input_to_send_to_taker) + u128::from(order.remaining_input_amount)),
+        )
+        .map_err(|_| dbg_msg!(LimoError::MathOverflow))?;
+        order.tip_amount = order
+            .tip_amount
+            .checked_sub(unfilled_tip_refund)
+            .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+        global_config.total_tip_amount = global_config
+            .total_tip_amount
+            .checked_sub(unfilled_tip_refund)
+            .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
+        calcs.unfilled_tip_refund = unfilled_tip_refund;
+
+        order.status = OrderStatus::Cancelled as u8;
+        global_config.open_order_count = global_config.open_order_count.saturating_sub(1);
     }
     order.last_updated_timestamp = current_timestamp.try_into().expect("Negative timestamp");
-    Ok(())
+
+    if let Some(vwap_oracle) = vwap_oracle {
+        update_vwap_oracle(
+            vwap_oracle,
+            input_to_send_to_taker,
+            output_to_send_to_maker,
+            order.last_updated_timestamp,
+        );
+    }
+
+    if let Some(maker_volume) = maker_volume {
+        record_maker_volume(
+            global_config,
+            order,
+            maker_volume,
+            input_to_send_to_taker,
+            output_to_send_to_maker,
+            host_tip,
+            current_timestamp,
+        )?;
+    }
+
+    if let Some(maker_analytics) = maker_analytics {
+        record_maker_analytics_fill(
+            order,
+            maker_analytics,
+            input_to_send_to_taker,
+            output_to_send_to_maker,
+            maker_tip,
+        )?;
+    }
+
+    #[cfg(any(test, feature = "test-bpf"))]
+    {
+        debug_assert_order_invariants(order);
+        debug_assert_global_config_invariants(global_config);
+    }
+
+    Ok(calcs)
+}
+
+#[cfg(any(test, feature = "test-bpf"))]
+fn debug_assert_order_invariants(order: &Order) {
+    assert!(order.remaining_input_amount <= order.initial_input_amount);
+    assert!(order.filled_output_amount <= order.expected_output_amount.saturating_mul(3));
+    assert!(order.tip_amount < u64::MAX / 2);
+    assert!(order.number_of_fills <= order.initial_input_amount);
+}
+
+#[cfg(any(test, feature = "test-bpf"))]
+fn debug_assert_global_config_invariants(global_config: &GlobalConfig) {
+    assert!(global_config.host_tip_amount <= global_config.total_tip_amount);
 }
 
-fn tip_calcs(global_config: &GlobalConfig, tip_amount: u64) -> Result<TipCalcs> {
-    let host_tip = (Fraction::from_bps(global_config.host_fee_bps) * Fraction::from(tip_amount))
-        .to_ceil::<u64>();
+fn tip_calcs(
+    global_config: &GlobalConfig,
+    order: &Order,
+    tip_amount: u64,
+    input_amount: u64,
+    mint_fee_config: Option<&MintFeeConfig>,
+    has_referrer: bool,
+) -> Result<TipCalcs> {
+    let host_fee_bps = if order.fee_tier == FEE_TIER_PREMIUM {
+        global_config.fee_tier_premium_host_fee_bps
+    } else {
+        match mint_fee_config {
+            Some(mint_fee_config) if mint_fee_config.enabled != 0 => mint_fee_config.host_fee_bps,
+            _ => global_config.host_fee_bps,
+        }
+    };
+    let host_tip_fraction = Fraction::from_bps(host_fee_bps) * Fraction::from(tip_amount);
+    let host_tip = if global_config.host_fee_rounding_mode == HOST_FEE_ROUNDING_MODE_FLOOR {
+        host_tip_fraction.to_floor::<u64>()
+    } else {
+        host_tip_fraction.to_ceil::<u64>()
+    };
 
     let maker_tip = tip_amount
         .checked_sub(host_tip)
         .ok_or_else(|| dbg_msg!(LimoError::MathOverflow))?;
 
+    let referrer_tip = if has_referrer {
+        (Fraction::from_bps(global_config.referrer_fee_bps) * Fraction::from(tip_amount))
+            .to_floor::<u64>()
+            .min(maker_tip)
+    } else {
+        0
+    };
+    let maker_tip = maker_tip - referrer_tip;
+
+    let boost_tip = if order.tip_boost_lamports > 0 && order.remaining_input_amount > 0 {
+        let boost_tip_u128 = u128::from(order.tip_boost_lamports) * u128::from(input_amount)
+            / u128::from(order.remaining_input_amount);
+        u64::try_from(boost_tip_u128).map_err(|_| dbg_msg!(LimoError::MathOverflow))?
+    } else {
+        0
+    };
+
     Ok(TipCalcs {
         host_tip,
         maker_tip,
+        boost_tip,
+        referrer_tip,
+        unfilled_tip_refund: 0,
     })
 }
 
@@ -467,6 +2212,26 @@ fn update_global_config_flag(
         UpdateGlobalConfigMode::UpdateOrderTakingPermissionless => {
             msg!("Field deprecated");
         }
+        UpdateGlobalConfigMode::UpdateExpressRelayDisabled => {
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.express_relay_disabled,
+            );
+            global_config.express_relay_disabled = value;
+        }
+        UpdateGlobalConfigMode::UpdateMultisigEnabled => {
+            msg!("new={} prev={}", value, global_config.multisig_enabled,);
+            global_config.multisig_enabled = value;
+        }
+        UpdateGlobalConfigMode::UpdateHostFeeRoundingMode => {
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.host_fee_rounding_mode,
+            );
+            global_config.host_fee_rounding_mode = value;
+        }
         _ => return Err(LimoError::InvalidConfigOption.into()),
     }
 
@@ -490,8 +2255,768 @@ fn update_global_config_pubkey(
             );
             global_config.admin_authority_cached = value;
         }
+        UpdateGlobalConfigMode::UpdateFeeTierNftMint => {
+            msg!("new={} prev={}", value, global_config.fee_tier_nft_mint,);
+            global_config.fee_tier_nft_mint = value;
+        }
+        UpdateGlobalConfigMode::UpdateApprovedTransferHook0
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook1
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook2
+        | UpdateGlobalConfigMode::UpdateApprovedTransferHook3 => {
+            let idx = mode as usize - UpdateGlobalConfigMode::UpdateApprovedTransferHook0 as usize;
+            msg!(
+                "new={} prev={}",
+                value,
+                global_config.approved_transfer_hooks[idx],
+            );
+            global_config.approved_transfer_hooks[idx] = value;
+        }
+        UpdateGlobalConfigMode::UpdateFeeCollector => {
+            msg!("new={} prev={}", value, global_config.fee_collector,);
+            global_config.fee_collector = value;
+        }
         _ => return Err(LimoError::InvalidConfigOption.into()),
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_order(input_amount: u64, output_amount: u64) -> Order {
+        Order {
+            initial_input_amount: input_amount,
+            remaining_input_amount: input_amount,
+            expected_output_amount: output_amount,
+            status: OrderStatus::Active as u8,
+            ..Order::default()
+        }
+    }
+
+    #[test]
+    fn vwap_fill_price_across_three_fills() {
+        let mut global_config = GlobalConfig::default();
+        let mut order = active_order(300, 300);
+
+        for (input_amount, output_amount) in [(100, 100), (100, 150), (100, 200)] {
+            take_order(
+                &mut global_config,
+                &mut order,
+                input_amount,
+                0,
+                0,
+                0,
+                output_amount,
+                None,
+                None,
+                false,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(order.number_of_fills, 3);
+        assert_eq!(order.status, OrderStatus::Filled as u8);
+        assert_eq!(compute_vwap_fill_price(&order), Some((450, 300)));
+    }
+
+    #[test]
+    fn vwap_fill_price_unfilled_order() {
+        assert_eq!(compute_vwap_fill_price(&active_order(300, 300)), None);
+    }
+
+    #[test]
+    fn order_age_seconds_saturates() {
+        let mut order = active_order(100, 100);
+        order.created_timestamp = 1_000;
+
+        assert_eq!(order_age_seconds(&order, 1_500), 500);
+        assert_eq!(order_age_seconds(&order, 1_000), 0);
+        assert_eq!(order_age_seconds(&order, 900), 0);
+    }
+
+    #[test]
+    fn slots_to_expiry_handles_unset_and_expired() {
+        let mut order = active_order(100, 100);
+        assert_eq!(slots_to_expiry(&order, 50), None);
+
+        order.expiry_slot = 100;
+        assert_eq!(slots_to_expiry(&order, 40), Some(60));
+        assert_eq!(slots_to_expiry(&order, 100), Some(0));
+        assert_eq!(slots_to_expiry(&order, 150), Some(0));
+    }
+
+    #[test]
+    fn time_to_expiry_seconds_handles_unset_and_expired() {
+        let mut order = active_order(100, 100);
+        assert_eq!(time_to_expiry_seconds(&order, 1_000), None);
+
+        order.expiry_timestamp = 1_000;
+        assert_eq!(time_to_expiry_seconds(&order, 400), Some(600));
+        assert_eq!(time_to_expiry_seconds(&order, 1_000), Some(0));
+        assert_eq!(time_to_expiry_seconds(&order, 1_500), Some(0));
+    }
+
+    #[test]
+    fn timestamp_expiry_rejects_take_and_allows_keeper_close() {
+        let mut gc = GlobalConfig {
+            open_order_count: 1,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        order.expiry_timestamp = 1_000;
+        assert!(!order.is_expired(0, 1_000));
+        assert!(order.is_expired(0, 1_001));
+
+        let res = take_order(
+            &mut gc, &mut order, 50, 0, 1_001, 0, 50, None, None, false, None, None, None, None,
+        );
+        assert!(res.is_err());
+
+        keeper_close_slot_expired_order(&mut order, &mut gc, 0, 1_001).unwrap();
+        assert_eq!(order.status, OrderStatus::Expired as u8);
+    }
+
+    #[test]
+    fn max_fills_rejects_fill_past_cap() {
+        let mut gc = GlobalConfig {
+            open_order_count: 1,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        order.max_fills = 1;
+
+        take_order(
+            &mut gc, &mut order, 50, 0, 0, 0, 50, None, None, false, None, None, None, None,
+        )
+        .unwrap();
+        assert_eq!(order.number_of_fills, 1);
+
+        let res = take_order(
+            &mut gc, &mut order, 50, 0, 0, 0, 50, None, None, false, None, None, None, None,
+        );
+        assert_eq!(res.err(), Some(LimoError::MaxFillsReached.into()));
+    }
+
+    #[test]
+    fn exclusive_taker_window_ends_after_configured_seconds() {
+        let mut order = active_order(100, 100);
+        order.created_timestamp = 1_000;
+        assert!(!order.is_in_exclusive_taker_window(1_000));
+
+        order.exclusive_taker_window_seconds = 30;
+        assert!(order.is_in_exclusive_taker_window(1_000));
+        assert!(order.is_in_exclusive_taker_window(1_029));
+        assert!(!order.is_in_exclusive_taker_window(1_030));
+    }
+
+    #[test]
+    fn maker_close_cooldown_applies_once_per_batch() {
+        let mut gc = GlobalConfig {
+            open_order_count: 2,
+            maker_close_cooldown_seconds: 60,
+            ..GlobalConfig::default()
+        };
+        let mut maker_activity = MakerActivity::default();
+        let mut first = active_order(100, 100);
+        let mut second = active_order(100, 100);
+
+        record_maker_close(&gc, &mut maker_activity, 1_000).unwrap();
+        close_order_and_claim_tip(&mut first, &mut gc, 1_000).unwrap();
+        close_order_and_claim_tip(&mut second, &mut gc, 1_000).unwrap();
+        assert_eq!(gc.open_order_count, 0);
+        assert_eq!(maker_activity.last_close_timestamp, 1_000);
+
+        assert!(record_maker_close(&gc, &mut maker_activity, 1_059).is_err());
+        record_maker_close(&gc, &mut maker_activity, 1_060).unwrap();
+    }
+
+    #[test]
+    fn expired_order_fails_take_and_is_expired_by_keeper() {
+        let mut gc = GlobalConfig {
+            open_order_count: 1,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        order.expiry_timestamp = 1_000;
+
+        let res = take_order_calcs(&gc, &order, 50, 50, 0, 1_001, None, None);
+        assert_eq!(res.err(), Some(LimoError::OrderExpired.into()));
+        assert_eq!(order.status, OrderStatus::Active as u8);
+
+        keeper_close_slot_expired_order(&mut order, &mut gc, 0, 1_001).unwrap();
+        assert_eq!(order.status, OrderStatus::Expired as u8);
+        assert_eq!(gc.open_order_count, 0);
+    }
+
+    #[test]
+    fn vault_dust_excludes_outstanding_escrow() {
+        let mut vault_escrow = VaultEscrow::default();
+        add_vault_escrow(&mut vault_escrow, 1_000).unwrap();
+        add_vault_escrow(&mut vault_escrow, 500).unwrap();
+        release_vault_escrow(&mut vault_escrow, 300).unwrap();
+
+        assert_eq!(vault_escrow.outstanding_amount, 1_200);
+        assert_eq!(compute_vault_dust(1_250, &vault_escrow), 50);
+        assert_eq!(compute_vault_dust(1_000, &vault_escrow), 0);
+        assert_eq!(
+            release_vault_escrow(&mut vault_escrow, 1_201),
+            Err(LimoError::VaultDustCalculationOverflow.into())
+        );
+    }
+
+    #[test]
+    fn counterparty_matching_allows_primary_and_additional_takers() {
+        use crate::utils::constraints::is_counterparty_matching;
+
+        let counterparty = Pubkey::new_unique();
+        let additional = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let additional_counterparties = [additional, Pubkey::default()];
+
+        assert!(is_counterparty_matching(&Pubkey::default(), &[], &stranger));
+        assert!(is_counterparty_matching(
+            &counterparty,
+            &additional_counterparties,
+            &counterparty
+        ));
+        assert!(is_counterparty_matching(
+            &counterparty,
+            &additional_counterparties,
+            &additional
+        ));
+        assert!(!is_counterparty_matching(
+            &counterparty,
+            &additional_counterparties,
+            &stranger
+        ));
+        assert!(!is_counterparty_matching(
+            &counterparty,
+            &additional_counterparties,
+            &Pubkey::default()
+        ));
+    }
+
+    #[test]
+    fn update_counterparty_rejects_pda_authority_and_clears_on_default() {
+        let global_config = GlobalConfig {
+            pda_authority: Pubkey::new_unique(),
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        let mut order_counterparties = OrderCounterparties::default();
+
+        for mode in [
+            UpdateOrderMode::UpdateCounterparty,
+            UpdateOrderMode::UpdateAdditionalCounterparty0,
+            UpdateOrderMode::UpdateAdditionalCounterparty1,
+            UpdateOrderMode::UpdateAdditionalCounterparty2,
+        ] {
+            assert_eq!(
+                update_order(
+                    &mut order,
+                    &global_config,
+                    Some(&mut order_counterparties),
+                    mode,
+                    global_config.pda_authority.as_ref(),
+                    0,
+                ),
+                Err(LimoError::InvalidCounterparty.into())
+            );
+        }
+        assert_eq!(order.counterparty, Pubkey::default());
+        assert_eq!(
+            order_counterparties.additional_counterparties,
+            [Pubkey::default(); 3]
+        );
+
+        let counterparty = Pubkey::new_unique();
+        update_order(
+            &mut order,
+            &global_config,
+            None,
+            UpdateOrderMode::UpdateCounterparty,
+            counterparty.as_ref(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(order.counterparty, counterparty);
+
+        update_order(
+            &mut order,
+            &global_config,
+            None,
+            UpdateOrderMode::UpdateCounterparty,
+            Pubkey::default().as_ref(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(order.counterparty, Pubkey::default());
+    }
+
+    #[test]
+    fn oracle_bounds_require_the_pair_price_feed() {
+        use crate::utils::oracle::{check_oracle_feed_for_pair, OraclePrice};
+
+        let pair_oracle_config = PairOracleConfig {
+            price_feed_id: [7; 32],
+            ..PairOracleConfig::default()
+        };
+        let oracle_price = |feed_id| OraclePrice {
+            feed_id,
+            price: 100,
+            exponent: 0,
+            publish_time: 0,
+        };
+
+        assert!(check_oracle_feed_for_pair(None, None, 0).is_ok());
+        assert_eq!(
+            check_oracle_feed_for_pair(Some(&oracle_price([7; 32])), None, 50),
+            Err(LimoError::OraclePriceFeedNotConfigured.into())
+        );
+        assert_eq!(
+            check_oracle_feed_for_pair(None, Some(&pair_oracle_config), 50),
+            Err(LimoError::OraclePriceAccountRequired.into())
+        );
+        assert_eq!(
+            check_oracle_feed_for_pair(Some(&oracle_price([8; 32])), Some(&pair_oracle_config), 50),
+            Err(LimoError::InvalidOraclePriceAccount.into())
+        );
+        assert!(check_oracle_feed_for_pair(
+            Some(&oracle_price([7; 32])),
+            Some(&pair_oracle_config),
+            50
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn close_delay_is_capped_at_the_delay_recorded_at_creation() {
+        let mut gc = GlobalConfig {
+            open_order_count: 2,
+            order_close_delay_seconds: 1_000,
+            ..GlobalConfig::default()
+        };
+
+        let mut order = active_order(100, 100);
+        order.close_delay_at_creation = 100;
+        assert_eq!(
+            close_order_and_claim_tip(&mut order, &mut gc, 99),
+            Err(LimoError::NotEnoughTimePassedSinceLastUpdate.into())
+        );
+        close_order_and_claim_tip(&mut order, &mut gc, 100).unwrap();
+
+        let mut legacy_order = active_order(100, 100);
+        assert_eq!(
+            close_order_and_claim_tip(&mut legacy_order, &mut gc, 100),
+            Err(LimoError::NotEnoughTimePassedSinceLastUpdate.into())
+        );
+        close_order_and_claim_tip(&mut legacy_order, &mut gc, 1_000).unwrap();
+    }
+
+    #[test]
+    fn multisig_can_only_be_enabled_with_a_multisig_admin() {
+        assert_eq!(
+            check_multisig_admin_can_take_over(None),
+            Err(LimoError::MultisigAdminRequired.into())
+        );
+        assert_eq!(
+            check_multisig_admin_can_take_over(Some(&MultiSigAdmin::default())),
+            Err(LimoError::InvalidMultisigThreshold.into())
+        );
+        assert!(check_multisig_admin_can_take_over(Some(&MultiSigAdmin {
+            threshold: 1,
+            ..MultiSigAdmin::default()
+        }))
+        .is_ok());
+    }
+
+    #[test]
+    fn admin_force_cancel_releases_expired_and_cancelled_orders() {
+        let mut gc = GlobalConfig {
+            open_order_count: 1,
+            total_tip_amount: 30,
+            ..GlobalConfig::default()
+        };
+
+        let mut expired = active_order(100, 100);
+        expired.status = OrderStatus::Expired as u8;
+        expired.tip_amount = 10;
+        admin_force_cancel_order(&mut expired, &mut gc).unwrap();
+        assert_eq!(expired.status, OrderStatus::Expired as u8);
+        assert_eq!(gc.open_order_count, 1);
+
+        let mut cancelled_ioc = active_order(100, 100);
+        cancelled_ioc.status = OrderStatus::Cancelled as u8;
+        cancelled_ioc.remaining_input_amount = 40;
+        admin_force_cancel_order(&mut cancelled_ioc, &mut gc).unwrap();
+
+        let mut active = active_order(100, 100);
+        active.tip_amount = 20;
+        admin_force_cancel_order(&mut active, &mut gc).unwrap();
+        assert_eq!(active.status, OrderStatus::Cancelled as u8);
+        assert_eq!(gc.open_order_count, 0);
+        assert_eq!(gc.total_tip_amount, 0);
+
+        let mut drained = active_order(100, 100);
+        drained.status = OrderStatus::Expired as u8;
+        drained.remaining_input_amount = 0;
+        assert_eq!(
+            admin_force_cancel_order(&mut drained, &mut gc),
+            Err(LimoError::OrderCanNotBeCanceled.into())
+        );
+    }
+
+    #[test]
+    fn update_order_is_rejected_within_flash_operation() {
+        let mut order = active_order(100, 100);
+        order.flash_ix_lock = 1;
+        let gc = GlobalConfig::default();
+
+        assert_eq!(
+            update_order(
+                &mut order,
+                &gc,
+                None,
+                UpdateOrderMode::UpdatePermissionless,
+                &[1],
+                0
+            ),
+            Err(LimoError::OrderWithinFlashOperation.into())
+        );
+        assert_eq!(order.permissionless, 0);
+    }
+
+    #[test]
+    fn fill_or_kill_reverts_partial_fill_and_accepts_exact_fill() {
+        let mut global_config = GlobalConfig::default();
+        let mut order = active_order(100, 100);
+        order.order_type = OrderType::FillOrKill.into();
+
+        let res = take_order(
+            &mut global_config,
+            &mut order,
+            50,
+            0,
+            0,
+            0,
+            50,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(res.err(), Some(LimoError::OrderInputAmountTooLarge.into()));
+        assert_eq!(order.remaining_input_amount, 100);
+        assert_eq!(order.number_of_fills, 0);
+
+        take_order(
+            &mut global_config,
+            &mut order,
+            100,
+            0,
+            0,
+            0,
+            100,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(order.status, OrderStatus::Filled as u8);
+    }
+
+    #[test]
+    fn min_tip_rejects_zero_tip_on_permissionless_take() {
+        let global_config = GlobalConfig {
+            min_tip_amount: 1,
+            ..GlobalConfig::default()
+        };
+
+        assert_eq!(
+            check_min_tip(&global_config, 0, false),
+            Err(LimoError::TipBelowMinimum.into())
+        );
+        assert_eq!(check_min_tip(&global_config, 1, false), Ok(()));
+        assert_eq!(check_min_tip(&global_config, 0, true), Ok(()));
+    }
+
+    #[test]
+    fn filled_order_can_be_closed_immediately() {
+        let mut global_config = GlobalConfig {
+            order_close_delay_seconds: 3_600,
+            min_order_lifetime_seconds: 3_600,
+            open_order_count: 1,
+            total_tip_amount: 10,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        order.close_delay_at_creation = 3_600;
+        order.created_timestamp = 1_000;
+        order.last_updated_timestamp = 1_000;
+        order.tip_amount = 10;
+        order.remaining_input_amount = 0;
+        order.filled_output_amount = 100;
+        order.status = OrderStatus::Filled as u8;
+
+        close_order_and_claim_tip(&mut order, &mut global_config, 1_000).unwrap();
+        assert_eq!(order.status, OrderStatus::Cancelled as u8);
+        assert_eq!(global_config.open_order_count, 0);
+        assert_eq!(global_config.total_tip_amount, 0);
+    }
+
+    #[test]
+    fn host_fee_uses_fee_tier_discount_and_rounding_mode() {
+        let mut global_config = GlobalConfig {
+            host_fee_bps: 5_000,
+            fee_tier_premium_host_fee_bps: 1_000,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+
+        let host_tip = |global_config: &GlobalConfig, order: &Order| {
+            tip_calcs(global_config, order, 15, 100, None, false)
+                .unwrap()
+                .host_tip
+        };
+
+        global_config.host_fee_rounding_mode = HOST_FEE_ROUNDING_MODE_FLOOR;
+        assert_eq!(host_tip(&global_config, &order), 7);
+        global_config.host_fee_rounding_mode = 0;
+        assert_eq!(host_tip(&global_config, &order), 8);
+
+        order.fee_tier = FEE_TIER_PREMIUM;
+        global_config.host_fee_rounding_mode = HOST_FEE_ROUNDING_MODE_FLOOR;
+        assert_eq!(host_tip(&global_config, &order), 1);
+        global_config.host_fee_rounding_mode = 0;
+        assert_eq!(host_tip(&global_config, &order), 2);
+    }
+
+    #[test]
+    fn migrate_order_v2_only_backfills_unset_fields() {
+        let global_config = GlobalConfig {
+            order_close_delay_seconds: 600,
+            ..GlobalConfig::default()
+        };
+        let mut order = active_order(100, 100);
+        order.last_updated_timestamp = 1_000;
+
+        assert_eq!(
+            migrate_order_v2(&mut order, &global_config, 1_500, 3, 2_000),
+            Err(LimoError::InvalidExpiryTimestamp.into())
+        );
+
+        migrate_order_v2(&mut order, &global_config, 5_000, 3, 2_000).unwrap();
+        assert_eq!(order.expiry_timestamp, 5_000);
+        assert_eq!(order.max_fills, 3);
+        assert_eq!(order.created_timestamp, 1_000);
+        assert_eq!(order.close_delay_at_creation, 600);
+
+        migrate_order_v2(&mut order, &global_config, 9_000, 7, 2_000).unwrap();
+        assert_eq!(order.expiry_timestamp, 5_000);
+        assert_eq!(order.max_fills, 3);
+
+        order.status = OrderStatus::Filled as u8;
+        assert_eq!(
+            migrate_order_v2(&mut order, &global_config, 0, 0, 2_000),
+            Err(LimoError::OrderNotActive.into())
+        );
+    }
+
+    #[test]
+    fn order_book_snapshot_follows_fills_and_closes() {
+        let mut global_config = GlobalConfig::default();
+        let mut snapshot = OrderBookSnapshot::default();
+        let mut order = active_order(100, 200);
+        add_order_to_order_book_snapshot(&mut snapshot, &order, 1);
+
+        let order_before = order;
+        take_order(
+            &mut global_config,
+            &mut order,
+            40,
+            0,
+            0,
+            0,
+            80,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        update_order_book_snapshot(&mut snapshot, &order_before, &order, 2).unwrap();
+        assert_eq!(snapshot.total_remaining_input(), 60);
+        assert_eq!(snapshot.total_expected_output(), 200);
+        assert_eq!(snapshot.active_order_count, 1);
+
+        let order_before = order;
+        close_order_and_claim_tip(&mut order, &mut global_config, 0).unwrap();
+        update_order_book_snapshot(&mut snapshot, &order_before, &order, 3).unwrap();
+        assert_eq!(snapshot.total_remaining_input(), 0);
+        assert_eq!(snapshot.total_expected_output(), 0);
+        assert_eq!(snapshot.active_order_count, 0);
+        assert_eq!(snapshot.last_updated_slot, 3);
+
+        order.input_mint = Pubkey::new_unique();
+        assert_eq!(
+            update_order_book_snapshot(&mut snapshot, &order_before, &order, 4),
+            Err(LimoError::InvalidAccount.into())
+        );
+    }
+
+    #[test]
+    fn order_fill_percentage_bps_tracks_filled_input() {
+        let mut order = active_order(400, 400);
+        assert_eq!(order_fill_percentage_bps(&order), 0);
+
+        order.remaining_input_amount = 300;
+        assert_eq!(order_fill_percentage_bps(&order), 2_500);
+
+        order.remaining_input_amount = 0;
+        assert_eq!(order_fill_percentage_bps(&order), 10_000);
+
+        assert_eq!(order_fill_percentage_bps(&Order::default()), 0);
+    }
+
+    #[test]
+    fn is_order_above_min_fill_rejects_zero_output_fills() {
+        let order = active_order(1_000, 10);
+
+        assert!(is_order_above_min_fill(&order, 1));
+        assert!(is_order_above_min_fill(&order, 1_000));
+        assert!(!is_order_above_min_fill(&order, 0));
+        assert!(!is_order_above_min_fill(&order, 1_001));
+
+        let cancelled = Order {
+            status: OrderStatus::Cancelled as u8,
+            ..active_order(1_000, 10)
+        };
+        assert!(!is_order_above_min_fill(&cancelled, 100));
+    }
+
+    #[test]
+    fn immediate_or_cancel_refunds_unfilled_tip_share() {
+        let mut global_config = GlobalConfig {
+            open_order_count: 1,
+            ..GlobalConfig::default()
+        };
+        let mut order = Order {
+            order_type: OrderType::ImmediateOrCancel.into(),
+            ..active_order(400, 400)
+        };
+
+        let effects = take_order(
+            &mut global_config,
+            &mut order,
+            100,
+            1_000,
+            0,
+            0,
+            100,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(order.status, OrderStatus::Cancelled as u8);
+        assert_eq!(effects.boost_to_send_to_taker, 750);
+        assert_eq!(order.tip_amount, 250);
+        assert_eq!(global_config.total_tip_amount, 250);
+        assert_eq!(global_config.open_order_count, 0);
+    }
+
+    #[test]
+    fn immediate_or_cancel_full_fill_keeps_tip() {
+        let mut global_config = GlobalConfig::default();
+        let mut order = Order {
+            order_type: OrderType::ImmediateOrCancel.into(),
+            ..active_order(400, 400)
+        };
+
+        let effects = take_order(
+            &mut global_config,
+            &mut order,
+            400,
+            1_000,
+            0,
+            0,
+            400,
+            None,
+            None,
+            false,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(order.status, OrderStatus::Filled as u8);
+        assert_eq!(effects.boost_to_send_to_taker, 0);
+        assert_eq!(order.tip_amount, 1_000);
+        assert_eq!(global_config.total_tip_amount, 1_000);
+    }
+
+    #[test]
+    fn batch_withdraw_maker_tips_accounting() {
+        let mut global_config = GlobalConfig::default();
+        let mut orders = [
+            active_order(100, 100),
+            active_order(100, 100),
+            active_order(100, 100),
+        ];
+        let tips = [1_000, 0, 2_500];
+        let host_tip_amount = 700;
+        for (order, tip) in orders.iter_mut().zip(tips) {
+            order.tip_amount = tip;
+        }
+        global_config.host_tip_amount = host_tip_amount;
+        global_config.total_tip_amount = tips.iter().sum::<u64>() + host_tip_amount;
+        let pda_authority_balance = global_config.total_tip_amount + 1_000_000;
+
+        let mut total_lamports = 0;
+        for order in orders.iter_mut() {
+            total_lamports += withdraw_maker_tip(order, &mut global_config).unwrap();
+        }
+
+        assert_eq!(total_lamports, 3_500);
+        assert!(orders.iter().all(|order| order.tip_amount == 0));
+        assert_eq!(global_config.total_tip_amount, host_tip_amount);
+        assert_eq!(global_config.host_tip_amount, host_tip_amount);
+        assert!(pda_authority_balance - total_lamports >= global_config.total_tip_amount);
+    }
+
+    #[test]
+    fn batch_withdraw_maker_tips_rejects_flash_locked_order() {
+        let mut global_config = GlobalConfig::default();
+        let mut order = active_order(100, 100);
+        order.tip_amount = 1_000;
+        order.flash_ix_lock = 1;
+        global_config.total_tip_amount = 1_000;
+
+        assert!(withdraw_maker_tip(&mut order, &mut global_config).is_err());
+        assert_eq!(order.tip_amount, 1_000);
+        assert_eq!(global_config.total_tip_amount, 1_000);
+    }
+}
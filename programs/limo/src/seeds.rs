@@ -6,6 +6,39 @@ pub const EVENT_AUTHORITY: &[u8] = b"__event_authority";
 pub const REFERRER_SEED: &[u8] = b"referrer";
 pub const USER_SWAP_BALANCES_SEED: &[u8] = b"balances";
 pub const ASSERT_SWAP_BALANCES_SEED: &[u8] = b"assert_swap";
+pub const ASSERT_SWAP_BALANCES_V2_SEED: &[u8] = b"assert_swap_v2";
+pub const VWAP_ORACLE_SEED: &[u8] = b"vwap";
+pub const TEMP_WSOL_SEED: &[u8] = b"temp_wsol";
+pub const MAKER_ACTIVITY_SEED: &[u8] = b"maker_activity";
+pub const MAKER_ORDER_COUNT_SEED: &[u8] = b"maker_order_count";
+pub const VAULT_ESCROW_SEED: &[u8] = b"vault_escrow";
+pub const ORDER_SEED: &[u8] = b"order";
+pub const ORDER_TRANSFER_SEED: &[u8] = b"order_transfer";
+pub const MAKER_NONCE_SEED: &[u8] = b"maker_nonce";
+pub const MINT_FEE_CONFIG_SEED: &[u8] = b"mint_fee_config";
+pub const DUTCH_AUCTION_SEED: &[u8] = b"dutch_auction";
+pub const STOP_LIMIT_TRIGGER_SEED: &[u8] = b"stop_limit_trigger";
+pub const ALLOWED_PROGRAM_SEED: &[u8] = b"allowed_program";
+pub const ORDER_BOOK_SEED: &[u8] = b"order_book";
+pub const MAKER_VOLUME_SEED: &[u8] = b"maker_volume";
+pub const MAKER_ANALYTICS_SEED: &[u8] = b"maker_analytics";
+pub const MULTISIG_ADMIN_SEED: &[u8] = b"multisig_admin";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const ORDER_DELEGATE_SEED: &[u8] = b"order_delegate";
+pub const ORDER_COUNTERPARTIES_SEED: &[u8] = b"order_counterparties";
+pub const ORDER_OUTPUT_RECIPIENT_SEED: &[u8] = b"order_output_recipient";
+pub const SWAP_PROGRAM_REGISTRY_SEED: &[u8] = b"swap_registry";
+pub const PAIR_ORACLE_CONFIG_SEED: &[u8] = b"pair_oracle_config";
+
+/// Per-order side accounts, seeded by the order key, closed to the maker with the order
+pub const ORDER_SIDE_ACCOUNT_SEEDS: [&[u8]; 6] = [
+    REFERRER_SEED,
+    DUTCH_AUCTION_SEED,
+    ORDER_DELEGATE_SEED,
+    ORDER_COUNTERPARTIES_SEED,
+    STOP_LIMIT_TRIGGER_SEED,
+    ORDER_OUTPUT_RECIPIENT_SEED,
+];
 
 mod macros {
     #[macro_export]
@@ -15,6 +48,12 @@ mod macros {
         };
     }
     #[macro_export]
+    macro_rules! temp_wsol_seeds {
+        ($bump: expr, $order_key: expr) => {
+            &[TEMP_WSOL_SEED as &[u8], $order_key.as_ref(), &[$bump]]
+        };
+    }
+    #[macro_export]
     macro_rules! intermediary_seeds {
         ($bump: expr, $order_key: expr) => {
             &[
@@ -2,13 +2,22 @@ use anchor_lang::prelude::{Pubkey, *};
 use derivative::Derivative;
 use num_enum::TryFromPrimitive;
 
-use crate::{utils::consts::UPDATE_GLOBAL_CONFIG_BYTE_SIZE, LimoError};
+use crate::{
+    utils::consts::{
+        DEFAULT_MAX_FLASH_INTERMEDIARY_IXS, MAX_ADDITIONAL_COUNTERPARTIES,
+        MAX_APPROVED_TRANSFER_HOOKS, MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS, MAX_MULTISIG_SIGNERS,
+        MAX_REGISTERED_SWAP_PROGRAMS, UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE,
+        UPDATE_GLOBAL_CONFIG_BYTE_SIZE,
+    },
+    LimoError,
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum OrderStatus {
     Active = 0,
     Filled = 1,
     Cancelled = 2,
+    Expired = 3,
 }
 
 impl From<OrderStatus> for u8 {
@@ -17,6 +26,7 @@ impl From<OrderStatus> for u8 {
             OrderStatus::Active => 0,
             OrderStatus::Filled => 1,
             OrderStatus::Cancelled => 2,
+            OrderStatus::Expired => 3,
         }
     }
 }
@@ -27,6 +37,7 @@ impl From<u8> for OrderStatus {
             0 => OrderStatus::Active,
             1 => OrderStatus::Filled,
             2 => OrderStatus::Cancelled,
+            3 => OrderStatus::Expired,
             _ => panic!("Invalid OrderStatus"),
         }
     }
@@ -35,12 +46,20 @@ impl From<u8> for OrderStatus {
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum OrderType {
     Vanilla = 0,
+    FillOrKill = 1,
+    ImmediateOrCancel = 2,
+    DutchAuction = 3,
+    StopLimit = 4,
 }
 
 impl From<OrderType> for u8 {
     fn from(val: OrderType) -> Self {
         match val {
             OrderType::Vanilla => 0,
+            OrderType::FillOrKill => 1,
+            OrderType::ImmediateOrCancel => 2,
+            OrderType::DutchAuction => 3,
+            OrderType::StopLimit => 4,
         }
     }
 }
@@ -50,6 +69,10 @@ impl TryFrom<u8> for OrderType {
     fn try_from(val: u8) -> core::result::Result<Self, LimoError> {
         match val {
             0 => Ok(OrderType::Vanilla),
+            1 => Ok(OrderType::FillOrKill),
+            2 => Ok(OrderType::ImmediateOrCancel),
+            3 => Ok(OrderType::DutchAuction),
+            4 => Ok(OrderType::StopLimit),
             _ => Err(LimoError::OrderTypeInvalid),
         }
     }
@@ -80,8 +103,11 @@ pub struct Order {
     pub flash_ix_lock: u8,
 
     pub permissionless: u8,
-
-    pub padding0: [u8; 3],
+    pub immutable_once_partially_filled: u8,
+    pub fee_tier: u8,
+    /// When set, the order can only be filled through Express Relay, even if it
+    /// is also marked `permissionless` or restricted to a counterparty
+    pub express_relay_only: u8,
 
     pub last_updated_timestamp: u64,
 
@@ -89,7 +115,56 @@ pub struct Order {
 
     pub counterparty: Pubkey,
 
-    pub padding: [u64; 15],
+    pub expiry_timestamp: u64,
+    /// Caps the number of fills the order accepts, zero means unlimited
+    pub max_fills: u32,
+    /// Seconds after creation during which only the counterparty can take
+    pub exclusive_taker_window_seconds: u32,
+
+    pub pending_output_amount: u64,
+    pub pending_update_slot: u64,
+
+    /// Opaque maker annotation, ignored by all protocol logic
+    pub maker_note: [u8; 32],
+
+    pub expiry_slot: u64,
+
+    pub nonce: u64,
+
+    pub close_delay_at_creation: u64,
+
+    pub created_timestamp: u64,
+
+    /// Opaque maker categorization tags, ignored by all protocol logic
+    pub tags: [u8; 16],
+
+    /// Maker-funded lamports paid out to takers pro rata to the input they fill
+    pub tip_boost_lamports: u64,
+}
+
+impl Order {
+    pub fn maker_note_hex(&self) -> String {
+        self.maker_note
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    pub fn tags_hex(&self) -> String {
+        self.tags.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn is_expired(&self, current_slot: u64, current_timestamp: u64) -> bool {
+        (self.expiry_slot != 0 && current_slot > self.expiry_slot)
+            || (self.expiry_timestamp != 0 && current_timestamp > self.expiry_timestamp)
+    }
+
+    pub fn is_in_exclusive_taker_window(&self, current_timestamp: i64) -> bool {
+        let window_end = self
+            .created_timestamp
+            .saturating_add(self.exclusive_taker_window_seconds.into());
+        u64::try_from(current_timestamp).map_or(true, |ts| ts < window_end)
+    }
 }
 
 #[event]
@@ -108,6 +183,251 @@ pub struct OrderDisplay {
     pub status: u8,
 
     pub last_updated_timestamp: u64,
+
+    pub vwap_numerator: u64,
+    pub vwap_denominator: u64,
+
+    pub maker_note: String,
+
+    pub fill_receipt_hash: [u8; 32],
+
+    pub nonce: u64,
+
+    pub tags: String,
+
+    pub max_fills: u32,
+
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub order: Pubkey,
+}
+
+#[event]
+pub struct OrderTransferred {
+    pub order: Pubkey,
+    pub old_maker: Pubkey,
+    pub new_maker: Pubkey,
+    pub remaining_input_amount: u64,
+    pub expected_output_amount: u64,
+    pub filled_output_amount: u64,
+    pub status: u8,
+    pub last_updated_timestamp: u64,
+}
+
+#[event]
+pub struct TipWithdrawn {
+    pub order: Pubkey,
+    pub maker: Pubkey,
+    pub tip_amount: u64,
+}
+
+#[event]
+pub struct BatchTipWithdrawal {
+    pub total_lamports: u64,
+    pub order_count: u8,
+}
+
+#[event]
+pub struct BatchHostTipWithdrawn {
+    pub amounts: [u64; MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS],
+    pub count: u8,
+}
+
+#[event]
+pub struct FlashArgMismatch {
+    pub field: u8,
+    pub start_value: u64,
+    pub end_value: u64,
+}
+
+#[event]
+pub struct VaultClosed {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct DeprecatedFieldWriteAttempted {
+    pub mode: u16,
+    pub ts: u64,
+}
+
+#[event]
+pub struct ProtocolVersionUpdated {
+    pub old: u32,
+    pub new: u32,
+    pub ts: u64,
+}
+
+#[event]
+pub struct SwapProgramRegistered {
+    pub global_config: Pubkey,
+    pub swap_program: Pubkey,
+}
+
+#[event]
+pub struct DelegateSet {
+    pub order: Pubkey,
+    pub maker: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct OrderMigrated {
+    pub order: Pubkey,
+    pub maker: Pubkey,
+    pub expiry_timestamp: u64,
+    pub max_fills: u32,
+    pub referrer: Pubkey,
+    pub created_timestamp: u64,
+    pub close_delay_at_creation: u64,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct MakerActivity {
+    pub last_close_timestamp: u64,
+    pub lifetime_orders_created: u64,
+
+    pub padding: [u64; 8],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct MakerOrderCount {
+    pub count: u32,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct VaultEscrow {
+    pub global_config: Pubkey,
+    pub mint: Pubkey,
+    /// Input still owed to orders escrowed in the vault, the excess is dust
+    pub outstanding_amount: u64,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct AllowedProgram {
+    pub program_id: Pubkey,
+    pub enabled: u8,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct SwapProgramRegistry {
+    pub global_config: Pubkey,
+    pub swap_programs: [Pubkey; MAX_REGISTERED_SWAP_PROGRAMS],
+}
+
+impl SwapProgramRegistry {
+    pub fn is_registered(&self, swap_program: &Pubkey) -> bool {
+        *swap_program != Pubkey::default() && self.swap_programs.contains(swap_program)
+    }
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct MultiSigAdmin {
+    pub global_config: Pubkey,
+    pub signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+    pub threshold: u8,
+    pub nonce: u64,
+    pub bump: u8,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct Proposal {
+    pub multisig_admin: Pubkey,
+    pub proposer: Pubkey,
+    pub nonce: u64,
+    pub mode: u16,
+    pub value: [u8; UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE],
+    pub approvals: u8,
+    pub created_timestamp: u64,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct MakerNonceTracker {
+    pub current_nonce: u64,
+
+    pub padding: [u64; 3],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct DutchAuction {
+    pub order: Pubkey,
+    pub start_output_amount: u64,
+    pub end_output_amount: u64,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+    pub padding: [u64; 4],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct StopLimitTrigger {
+    pub order: Pubkey,
+    pub price_feed: Pubkey,
+    pub trigger_price_numerator: u64,
+    pub trigger_price_denominator: u64,
+    pub padding: [u64; 4],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderReferrer {
+    pub order: Pubkey,
+    pub referrer: Pubkey,
+    pub padding: [u64; 4],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderDelegate {
+    pub order: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderCounterparties {
+    pub order: Pubkey,
+    pub additional_counterparties: [Pubkey; MAX_ADDITIONAL_COUNTERPARTIES],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct PairOracleConfig {
+    pub global_config: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    /// Pyth feed whose price fills of the pair are bounded against
+    pub price_feed_id: [u8; 32],
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderOutputRecipient {
+    pub order: Pubkey,
+    pub recipient: Pubkey,
+}
+
+#[account]
+#[derive(Debug, Default)]
+pub struct OrderTransferProposal {
+    pub order: Pubkey,
+    pub pending_new_maker: Pubkey,
+    pub pending_transfer_timestamp: u64,
+
+    pub padding: [u64; 4],
 }
 
 #[derive(PartialEq, Derivative)]
@@ -117,6 +437,7 @@ pub struct UserSwapBalancesState {
     pub user_lamports: u64,
     pub input_ta_balance: u64,
     pub output_ta_balance: u64,
+    pub nonce: u64,
 }
 
 #[event]
@@ -137,6 +458,157 @@ pub struct UserSwapBalanceDiffs {
     pub next_best_aggregator: u8,
 }
 
+#[derive(PartialEq, Derivative)]
+#[derivative(Debug)]
+#[account(zero_copy)]
+pub struct VwapOracle {
+    pub global_config: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+
+    pub cumulative_input_volume: u64,
+    pub cumulative_output_volume: u64,
+    pub last_update_timestamp: u64,
+    pub window_seconds: u64,
+    pub vwap_numerator: u64,
+    pub vwap_denominator: u64,
+
+    pub padding: [u64; 10],
+}
+
+#[derive(PartialEq, Derivative, Default)]
+#[derivative(Debug)]
+#[account(zero_copy)]
+pub struct OrderBookSnapshot {
+    pub global_config: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+
+    pub total_remaining_input: [u64; 2],
+    pub total_expected_output: [u64; 2],
+    pub active_order_count: u64,
+    pub last_updated_slot: u64,
+
+    pub padding: [u64; 8],
+}
+
+impl OrderBookSnapshot {
+    pub fn total_remaining_input(&self) -> u128 {
+        u128::from(self.total_remaining_input[0])
+            | (u128::from(self.total_remaining_input[1]) << 64)
+    }
+
+    pub fn set_total_remaining_input(&mut self, value: u128) {
+        self.total_remaining_input = [value as u64, (value >> 64) as u64];
+    }
+
+    pub fn total_expected_output(&self) -> u128 {
+        u128::from(self.total_expected_output[0])
+            | (u128::from(self.total_expected_output[1]) << 64)
+    }
+
+    pub fn set_total_expected_output(&mut self, value: u128) {
+        self.total_expected_output = [value as u64, (value >> 64) as u64];
+    }
+}
+
+#[derive(PartialEq, Derivative)]
+#[derivative(Debug)]
+#[account(zero_copy)]
+pub struct MakerVolume {
+    pub global_config: Pubkey,
+    pub maker: Pubkey,
+    pub epoch: u64,
+
+    pub input_volume: [u64; 2],
+    pub output_volume: [u64; 2],
+    pub tip_paid: u64,
+    pub fill_count: u64,
+    pub rebate_claimed: u64,
+
+    pub padding: [u64; 8],
+}
+
+impl MakerVolume {
+    pub fn input_volume(&self) -> u128 {
+        u128::from(self.input_volume[0]) | (u128::from(self.input_volume[1]) << 64)
+    }
+
+    pub fn set_input_volume(&mut self, value: u128) {
+        self.input_volume = [value as u64, (value >> 64) as u64];
+    }
+
+    pub fn output_volume(&self) -> u128 {
+        u128::from(self.output_volume[0]) | (u128::from(self.output_volume[1]) << 64)
+    }
+
+    pub fn set_output_volume(&mut self, value: u128) {
+        self.output_volume = [value as u64, (value >> 64) as u64];
+    }
+}
+
+#[derive(PartialEq, Derivative)]
+#[derivative(Debug)]
+#[account(zero_copy)]
+pub struct MakerAnalytics {
+    pub global_config: Pubkey,
+    pub maker: Pubkey,
+
+    pub total_orders_created: u64,
+    pub total_orders_filled: u64,
+    pub total_orders_cancelled: u64,
+    pub total_input_volume: [u64; 2],
+    pub total_output_volume: [u64; 2],
+    pub total_tips_earned: u64,
+    pub first_order_ts: u64,
+    pub last_order_ts: u64,
+
+    pub padding: [u64; 8],
+}
+
+impl MakerAnalytics {
+    pub fn total_input_volume(&self) -> u128 {
+        u128::from(self.total_input_volume[0]) | (u128::from(self.total_input_volume[1]) << 64)
+    }
+
+    pub fn set_total_input_volume(&mut self, value: u128) {
+        self.total_input_volume = [value as u64, (value >> 64) as u64];
+    }
+
+    pub fn total_output_volume(&self) -> u128 {
+        u128::from(self.total_output_volume[0]) | (u128::from(self.total_output_volume[1]) << 64)
+    }
+
+    pub fn set_total_output_volume(&mut self, value: u128) {
+        self.total_output_volume = [value as u64, (value >> 64) as u64];
+    }
+}
+
+#[derive(PartialEq, Derivative)]
+#[derivative(Debug)]
+#[account(zero_copy)]
+pub struct MintFeeConfig {
+    pub mint: Pubkey,
+    pub global_config: Pubkey,
+    pub host_fee_bps: u16,
+    pub enabled: u8,
+    pub padding0: [u8; 5],
+
+    pub padding: [u64; 8],
+}
+
+#[event]
+pub struct VwapOracleDisplay {
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub cumulative_input_volume: u64,
+    pub cumulative_output_volume: u64,
+    pub last_update_timestamp: u64,
+    pub window_seconds: u64,
+    pub vwap_numerator: u64,
+    pub vwap_denominator: u64,
+}
+
 #[derive(PartialEq, Derivative)]
 #[derivative(Debug)]
 #[account(zero_copy)]
@@ -150,7 +622,12 @@ pub struct GlobalConfig {
 
     pub padding0: [u8; 2],
     pub order_close_delay_seconds: u64,
-    pub padding1: [u64; 9],
+    pub max_orders_per_maker: u32,
+    pub padding5: [u8; 4],
+    pub min_tip_amount: u64,
+    pub epoch_seconds: u64,
+    pub proposal_expiry_seconds: u64,
+    pub padding1: [u64; 5],
 
     pub pda_authority_previous_lamports_balance: u64,
     pub total_tip_amount: u64,
@@ -163,7 +640,74 @@ pub struct GlobalConfig {
     pub txn_fee_cost: u64,
     pub ata_creation_cost: u64,
 
-    pub padding2: [u64; 241],
+    pub order_update_delay_slots: u64,
+
+    pub open_order_count: u64,
+    pub total_volume_input: u64,
+    pub total_volume_output: u64,
+
+    pub express_relay_disabled: u8,
+    pub max_flash_intermediary_ixs: u8,
+    pub fee_tier_premium_host_fee_bps: u16,
+    pub referrer_fee_bps: u16,
+    pub multisig_enabled: u8,
+    pub host_fee_rounding_mode: u8,
+
+    pub maker_close_cooldown_seconds: u64,
+
+    pub fee_tier_nft_mint: Pubkey,
+
+    pub order_sequence: u64,
+
+    pub min_order_lifetime_seconds: u64,
+
+    pub transfer_finalization_delay_seconds: u64,
+
+    pub deprecated_order_types: u32,
+    pub oracle_price_tolerance_bps: u16,
+    pub volume_rebate_bps: u16,
+
+    pub approved_transfer_hooks: [Pubkey; MAX_APPROVED_TRANSFER_HOOKS],
+
+    pub protocol_version: u32,
+    pub padding6: [u8; 4],
+
+    pub fee_collector: Pubkey,
+
+    pub min_order_input_amount: u64,
+    pub max_order_input_amount: u64,
+
+    pub base_fee_lamports: u64,
+
+    pub padding2: [u64; 203],
+}
+
+impl GlobalConfig {
+    pub fn effective_max_flash_intermediary_ixs(&self) -> u8 {
+        if self.max_flash_intermediary_ixs == 0 {
+            DEFAULT_MAX_FLASH_INTERMEDIARY_IXS
+        } else {
+            self.max_flash_intermediary_ixs
+        }
+    }
+
+    pub fn effective_fee_collector(&self) -> Pubkey {
+        if self.fee_collector == Pubkey::default() {
+            self.admin_authority
+        } else {
+            self.fee_collector
+        }
+    }
+
+    pub fn is_order_input_amount_within_bounds(&self, input_amount: u64) -> bool {
+        (self.min_order_input_amount == 0 || input_amount >= self.min_order_input_amount)
+            && (self.max_order_input_amount == 0 || input_amount <= self.max_order_input_amount)
+    }
+
+    pub fn is_order_type_deprecated(&self, order_type: u8) -> bool {
+        1u32.checked_shl(u32::from(order_type))
+            .is_some_and(|bit| bit & self.deprecated_order_types != 0)
+    }
 }
 
 impl Default for GlobalConfig {
@@ -181,6 +725,10 @@ impl Default for GlobalConfig {
             orders_taking_blocked: 0,
             host_fee_bps: 0,
             order_close_delay_seconds: 0,
+            max_orders_per_maker: 0,
+            min_tip_amount: 0,
+            epoch_seconds: 0,
+            proposal_expiry_seconds: 0,
             pda_authority_previous_lamports_balance: 0,
             total_tip_amount: 0,
             host_tip_amount: 0,
@@ -191,21 +739,112 @@ impl Default for GlobalConfig {
             emergency_mode: 0,
             ata_creation_cost: 0,
             txn_fee_cost: 0,
+            order_update_delay_slots: 0,
+            open_order_count: 0,
+            total_volume_input: 0,
+            total_volume_output: 0,
+            express_relay_disabled: 0,
+            max_flash_intermediary_ixs: DEFAULT_MAX_FLASH_INTERMEDIARY_IXS,
+            maker_close_cooldown_seconds: 0,
+            fee_tier_premium_host_fee_bps: 0,
+            referrer_fee_bps: 0,
+            multisig_enabled: 0,
+            host_fee_rounding_mode: 0,
+            fee_tier_nft_mint: Pubkey::default(),
+            order_sequence: 0,
+            min_order_lifetime_seconds: 0,
+            transfer_finalization_delay_seconds: 0,
+            deprecated_order_types: 0,
+            oracle_price_tolerance_bps: 0,
+            volume_rebate_bps: 0,
+            approved_transfer_hooks: [Pubkey::default(); MAX_APPROVED_TRANSFER_HOOKS],
+            protocol_version: crate::utils::consts::CURRENT_PROTOCOL_VERSION,
+            fee_collector: Pubkey::default(),
+            min_order_input_amount: 0,
+            max_order_input_amount: 0,
+            base_fee_lamports: 0,
             padding0: [0; 2],
-            padding1: [0; 9],
-            padding2: [0; 241],
+            padding1: [0; 5],
+            padding2: [0; 203],
+            padding6: [0; 4],
+            padding5: [0; 4],
         }
     }
 }
 
+#[event]
+pub struct PendingFillsStatus {
+    pub pending_count: u8,
+    pub locked_orders: Vec<Pubkey>,
+    pub oldest_lock_slot: u64,
+    pub current_slot: u64,
+}
+
+#[event]
+pub struct FillRateEvent {
+    pub order: Pubkey,
+    pub fill_fraction_bps: u16,
+    pub seconds_elapsed: u64,
+    pub status: u8,
+}
+
+#[event]
+pub struct CostRecalculated {
+    pub old_ata_creation_cost: u64,
+    pub new_ata_creation_cost: u64,
+    pub old_txn_fee_cost: u64,
+    pub new_txn_fee_cost: u64,
+}
+
+#[event]
+pub struct GlobalConfigSnapshot {
+    pub emergency_mode: u8,
+    pub flash_blocked: u8,
+    pub new_orders_blocked: u8,
+    pub taking_blocked: u8,
+    pub host_fee_bps: u16,
+    pub order_close_delay_seconds: u64,
+    pub total_tip_amount: u64,
+    pub host_tip_amount: u64,
+    pub txn_fee_cost: u64,
+    pub ata_creation_cost: u64,
+    pub open_order_count: u64,
+    pub total_volume_input: u64,
+    pub total_volume_output: u64,
+    pub pda_authority: Pubkey,
+    pub admin_authority: Pubkey,
+    pub timestamp: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct TakeOrderBatchEntry {
+    pub order: Pubkey,
+    pub input_amount: u64,
+    pub min_output_amount: u64,
+    pub tip_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct GlobalConfigUpdateEntry {
+    pub mode: u16,
+    pub value: [u8; UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE],
+}
+
 pub struct TakeOrderEffects {
     pub input_to_send_to_taker: u64,
     pub output_to_send_to_maker: u64,
+    /// Lamports returned to the taker from the pda authority: the boost share
+    /// and, for an ImmediateOrCancel order, the unfilled tip refund
+    pub boost_to_send_to_taker: u64,
+    pub referrer_tip_to_send: u64,
 }
 
 pub struct TipCalcs {
     pub host_tip: u64,
     pub maker_tip: u64,
+    pub boost_tip: u64,
+    pub referrer_tip: u64,
+    pub unfilled_tip_refund: u64,
 }
 
 #[derive(TryFromPrimitive, PartialEq, Eq, Clone, Copy, Debug)]
@@ -221,6 +860,34 @@ pub enum UpdateGlobalConfigMode {
     UpdateOrderCloseDelaySeconds = 7,
     UpdateTxnFeeCost = 8,
     UpdateAtaCreationCost = 9,
+    UpdateMaxOrdersPerMaker = 10,
+    UpdateExpressRelayDisabled = 11,
+    UpdateMakerCloseCooldown = 12,
+    UpdateMaxFlashIntermediaryIxs = 13,
+    UpdateFeeTierNftMint = 14,
+    UpdateFeeTierPremiumHostFeeBps = 15,
+    UpdateMinOrderLifetimeSeconds = 16,
+    UpdateTransferFinalizationDelaySeconds = 17,
+    UpdateDeprecatedOrderTypes = 18,
+    UpdateReferrerFeeBps = 19,
+    UpdateOraclePriceToleranceBps = 20,
+    UpdateOrderUpdateDelaySlots = 21,
+    UpdateMinTipAmount = 22,
+    UpdateEpochSeconds = 23,
+    UpdateVolumeRebateBps = 24,
+    UpdateMultisigEnabled = 25,
+    UpdateMultisigThreshold = 26,
+    UpdateProposalExpirySeconds = 27,
+    UpdateHostFeeRoundingMode = 28,
+    UpdateApprovedTransferHook0 = 29,
+    UpdateApprovedTransferHook1 = 30,
+    UpdateApprovedTransferHook2 = 31,
+    UpdateApprovedTransferHook3 = 32,
+    UpdateProtocolVersion = 33,
+    UpdateFeeCollector = 34,
+    UpdateMinOrderInputAmount = 35,
+    UpdateMaxOrderInputAmount = 36,
+    UpdateBaseFeeLamports = 37,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -264,5 +931,16 @@ pub struct GetBalancesCheckedResult {
 #[repr(u16)]
 pub enum UpdateOrderMode {
     UpdatePermissionless = 0,
+    /// The default pubkey clears the counterparty
     UpdateCounterparty = 1,
+    UpdateExpectedOutputAmount = 2,
+    SetImmutableOncePartiallyFilled = 9,
+    UpdateMakerNote = 10,
+    SetExpressRelayOnly = 11,
+    UpdateTags = 12,
+    UpdateAdditionalCounterparty0 = 13,
+    UpdateAdditionalCounterparty1 = 14,
+    UpdateAdditionalCounterparty2 = 15,
+    UpdateMaxFills = 16,
+    UpdateExclusiveTakerWindowSeconds = 17,
 }
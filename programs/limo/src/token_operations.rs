@@ -1,8 +1,10 @@
 use anchor_lang::{
-    prelude::{AccountInfo, CpiContext},
-    Result,
+    err, error,
+    prelude::{msg, AccountInfo, CpiContext},
+    require_keys_eq, Result,
 };
 use anchor_spl::{
+    associated_token,
     token::{spl_token, TokenAccount},
     token_interface,
 };
@@ -10,10 +12,12 @@ use solana_program::{
     program::{invoke, invoke_signed},
     program_pack::Pack,
     rent::Rent,
-    system_instruction,
+    system_instruction, system_program,
     sysvar::Sysvar,
 };
 
+use crate::{utils::constraints::is_wsol, LimoError};
+
 #[allow(clippy::too_many_arguments)]
 pub fn transfer_from_user_to_token_account<'a>(
     user_token_account: AccountInfo<'a>,
@@ -142,6 +146,163 @@ pub fn close_ata_accounts_with_signer_seeds<'a>(
     Ok(())
 }
 
+pub fn create_associated_token_account<'a>(
+    payer: AccountInfo<'a>,
+    associated_token_account: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    associated_token_program: AccountInfo<'a>,
+) -> Result<()> {
+    associated_token::create(CpiContext::new(
+        associated_token_program,
+        associated_token::Create {
+            payer,
+            associated_token: associated_token_account,
+            authority,
+            mint,
+            system_program,
+            token_program,
+        },
+    ))?;
+
+    Ok(())
+}
+
+pub fn check_and_sync_native_if_needed<'a>(
+    token_account: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+) -> Result<()> {
+    if !is_wsol(mint.key) {
+        return Ok(());
+    }
+
+    token_interface::sync_native(CpiContext::new(
+        token_program.clone(),
+        token_interface::SyncNative {
+            account: token_account.clone(),
+        },
+    ))?;
+
+    Ok(())
+}
+
+pub fn close_token_account<'a>(
+    account_to_close: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new(
+        token_program,
+        token_interface::CloseAccount {
+            account: account_to_close,
+            destination,
+            authority,
+        },
+    ))?;
+
+    Ok(())
+}
+
+pub fn close_program_account<'a>(
+    account_to_close: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+) -> Result<()> {
+    let destination_lamports = destination.lamports();
+    **destination.lamports.borrow_mut() = destination_lamports
+        .checked_add(account_to_close.lamports())
+        .ok_or(LimoError::MathOverflow)?;
+    **account_to_close.lamports.borrow_mut() = 0;
+
+    account_to_close.assign(&system_program::ID);
+    account_to_close.realloc(0, false)?;
+
+    Ok(())
+}
+
+/// Closes a program owned PDA passed at its verified address, if it has been
+/// initialized
+pub fn close_program_account_if_initialized<'a>(
+    account_to_close: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+) -> Result<()> {
+    if account_to_close.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(
+        *account_to_close.owner,
+        crate::ID,
+        LimoError::InvalidAccount
+    );
+
+    close_program_account(account_to_close, destination)
+}
+
+pub fn initialize_wsol_token_account_with_native_sol<'a>(
+    token_account: AccountInfo<'a>,
+    native_mint: AccountInfo<'a>,
+    owner: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    token_account_signer_seeds: &[&[u8]],
+    sol_amount: u64,
+) -> Result<()> {
+    let rent_exempt_balance = Rent::get()?.minimum_balance(TokenAccount::LEN);
+
+    let create_ix = system_instruction::create_account(
+        owner.key,
+        token_account.key,
+        rent_exempt_balance,
+        TokenAccount::LEN as u64,
+        token_program.key,
+    );
+
+    invoke_signed(
+        &create_ix,
+        &[owner.clone(), token_account.clone()],
+        &[token_account_signer_seeds],
+    )?;
+
+    token_interface::initialize_account3(CpiContext::new(
+        token_program.clone(),
+        token_interface::InitializeAccount3 {
+            account: token_account.clone(),
+            mint: native_mint,
+            authority: owner.clone(),
+        },
+    ))?;
+
+    native_transfer_from_user_to_account(owner, token_account.clone(), sol_amount)?;
+
+    token_interface::sync_native(CpiContext::new(
+        token_program,
+        token_interface::SyncNative {
+            account: token_account,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// Lamports sent to the intermediary address by a third party are tolerated and
+/// topped up at initialization, but any leftover data means a previous close
+/// did not go through.
+pub fn validate_intermediary_cleanup(account: &AccountInfo) -> Result<()> {
+    if account.data_len() != 0 {
+        msg!(
+            "Intermediary account {} still holds {} bytes of data, lamports {}",
+            account.key,
+            account.data_len(),
+            account.lamports()
+        );
+        return err!(LimoError::StuckIntermediaryAccount);
+    }
+
+    Ok(())
+}
+
 pub fn initialize_intermediary_token_account_with_signer_seeds<'a>(
     intermediary_token_account: AccountInfo<'a>,
     mint: AccountInfo<'a>,
@@ -157,6 +318,24 @@ pub fn initialize_intermediary_token_account_with_signer_seeds<'a>(
         TokenAccount::LEN
     };
 
+    if intermediary_token_account.owner == token_program.key
+        && !intermediary_token_account.data_is_empty()
+    {
+        msg!(
+            "Closing stale intermediary token account {}",
+            intermediary_token_account.key
+        );
+        close_ata_accounts_with_signer_seeds(
+            intermediary_token_account.clone(),
+            authority.clone(),
+            authority.clone(),
+            token_program.clone(),
+            authority_signer_seeds,
+        )?;
+    } else {
+        validate_intermediary_cleanup(&intermediary_token_account)?;
+    }
+
     let rent_exempt_balance = Rent::get()?.minimum_balance(token_account_len);
     let current_lamports_balance = intermediary_token_account.lamports();
 
@@ -137,9 +137,12 @@ fn search_start_ix(
     end_ix_discriminator: &[u8; 8],
 ) -> Result<Instruction> {
     let mut found_start_ix = None;
+    let Some(start_from) = current_idx.checked_sub(1) else {
+        return err!(LimoError::FlashIxsNotStarted);
+    };
 
-    for idx in (0..current_idx).rev() {
-        let ix = instruction_loader.load_instruction_at(idx)?;
+    for ix in ix_utils::IxIterator::new_backward(start_from, instruction_loader) {
+        let ix = ix?;
         if ix.program_id == crate::id() {
             let discriminator = &ix.data[..8];
             if discriminator.eq(start_ix_discriminator) {
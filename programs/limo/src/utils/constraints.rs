@@ -7,7 +7,7 @@ use anchor_spl::{
 };
 use express_relay::{cpi::accounts::CheckPermission, sdk::cpi::check_permission_cpi};
 
-use crate::{GlobalConfig, LimoError};
+use crate::{GlobalConfig, LimoError, OrderDelegate, OrderOutputRecipient, VwapOracle};
 
 pub fn emergency_mode_disabled(global_config: &AccountLoader<GlobalConfig>) -> Result<()> {
     if global_config.load()?.emergency_mode > 0 {
@@ -16,6 +16,13 @@ pub fn emergency_mode_disabled(global_config: &AccountLoader<GlobalConfig>) -> R
     Ok(())
 }
 
+pub fn emergency_mode_enabled(global_config: &AccountLoader<GlobalConfig>) -> Result<()> {
+    if global_config.load()?.emergency_mode == 0 {
+        return err!(LimoError::EmergencyModeDisabled);
+    }
+    Ok(())
+}
+
 pub fn flash_taking_orders_disabled(global_config: &AccountLoader<GlobalConfig>) -> Result<()> {
     if global_config.load()?.flash_take_order_blocked > 0 {
         return err!(LimoError::FlashTakeOrderBlocked);
@@ -88,12 +95,112 @@ pub fn is_wsol(mint: &Pubkey) -> bool {
     *mint == token::spl_token::native_mint::ID
 }
 
-pub fn is_counterparty_matching(counterparty: &Pubkey, taker: &Pubkey) -> bool {
-    counterparty.eq(&Pubkey::default()) || taker == counterparty
+pub fn get_vwap_oracle_checked<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    global_config: &Pubkey,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+) -> Result<Option<AccountLoader<'info, VwapOracle>>> {
+    let Some(vwap_oracle_account) = remaining_accounts.first() else {
+        return Ok(None);
+    };
+
+    let vwap_oracle = AccountLoader::<VwapOracle>::try_from(vwap_oracle_account)?;
+    {
+        let vwap_oracle_state = vwap_oracle.load()?;
+        require!(
+            vwap_oracle_state.global_config == *global_config
+                && vwap_oracle_state.input_mint == *input_mint
+                && vwap_oracle_state.output_mint == *output_mint,
+            LimoError::InvalidVwapOracle
+        );
+    }
+
+    Ok(Some(vwap_oracle))
+}
+
+/// An unset (default) counterparty means the order is open to any taker.
+/// Otherwise the taker must be the counterparty or one of the non-default
+/// additional counterparties.
+pub fn is_counterparty_matching(
+    counterparty: &Pubkey,
+    additional_counterparties: &[Pubkey],
+    taker: &Pubkey,
+) -> bool {
+    if *counterparty == Pubkey::default() {
+        return true;
+    }
+
+    taker == counterparty
+        || additional_counterparties
+            .iter()
+            .any(|c| *c != Pubkey::default() && c == taker)
+}
+
+/// Deserializes an optional side PDA passed at its verified address, returning
+/// `None` when it has not been initialized.
+pub fn load_if_initialized<T: AccountDeserialize>(account: &AccountInfo) -> Result<Option<T>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*account.owner, crate::ID, LimoError::InvalidAccount);
+
+    let data = account.try_borrow_data()?;
+    Ok(Some(T::try_deserialize(&mut &data[..])?))
+}
+
+/// Applies `update` to an optional side PDA passed at its verified address and
+/// writes it back, doing nothing when it has not been initialized.
+pub fn update_if_initialized<T: AccountSerialize + AccountDeserialize>(
+    account: &AccountInfo,
+    update: impl FnOnce(&mut T) -> Result<()>,
+) -> Result<()> {
+    let Some(mut state) = load_if_initialized::<T>(account)? else {
+        return Ok(());
+    };
+    update(&mut state)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Returns the wallet an order's output is paid to: the recipient when the order's
+/// output recipient PDA is initialized, the maker otherwise.
+pub fn get_order_output_owner(
+    order_output_recipient: &AccountInfo,
+    maker: &Pubkey,
+) -> Result<Pubkey> {
+    Ok(
+        load_if_initialized::<OrderOutputRecipient>(order_output_recipient)?
+            .map_or(*maker, |order_output_recipient| {
+                order_output_recipient.recipient
+            }),
+    )
+}
+
+pub fn signer_is_maker_or_delegate(
+    maker: &AccountInfo,
+    delegate: Option<&Signer>,
+    order_delegate: Option<&OrderDelegate>,
+) -> Result<()> {
+    if maker.is_signer {
+        return Ok(());
+    }
+
+    match (delegate, order_delegate) {
+        (Some(delegate), Some(order_delegate))
+            if order_delegate.delegate != Pubkey::default()
+                && delegate.key() == order_delegate.delegate =>
+        {
+            Ok(())
+        }
+        _ => err!(LimoError::MakerOrDelegateSignatureRequired),
+    }
 }
 
 pub mod token_2022 {
-    use anchor_lang::{err, Key};
+    use anchor_lang::{err, error, Key};
     use anchor_spl::{
         token::spl_token,
         token_2022::{
@@ -104,7 +211,7 @@ pub mod token_2022 {
         },
     };
     use bytemuck::Zeroable;
-    use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+    use solana_program::{account_info::AccountInfo, clock::Epoch, pubkey::Pubkey};
 
     use crate::{xmsg, LimoError};
 
@@ -118,15 +225,18 @@ pub mod token_2022 {
         ExtensionType::TokenMetadata,
         ExtensionType::TransferHook,
         ExtensionType::DefaultAccountState,
+        ExtensionType::InterestBearingConfig,
     ];
 
+    /// Returns the highest transfer fee, in bps, the mint can currently charge
     pub fn validate_token_extensions(
         mint_acc_info: &AccountInfo,
         token_acc_infos: Vec<&AccountInfo>,
         is_close_order_and_claim_tip_ix: bool,
-    ) -> anchor_lang::Result<()> {
+        transfer_hook_whitelist: &[Pubkey],
+    ) -> anchor_lang::Result<u16> {
         if mint_acc_info.owner == &spl_token::id() {
-            return Ok(());
+            return Ok(0);
         }
 
         let mint_data = mint_acc_info.data.borrow();
@@ -147,6 +257,7 @@ pub mod token_2022 {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
+        let mut transfer_fee_bps = 0;
         for mint_ext in mint.get_extension_types()? {
             if !VALID_LIQUIDITY_TOKEN_EXTENSIONS.contains(&mint_ext) {
                 xmsg!(
@@ -160,19 +271,17 @@ pub mod token_2022 {
                 let ext = mint
                     .get_extension::<spl_token_2022::extension::transfer_fee::TransferFeeConfig>(
                     )?;
-                if <u16>::from(ext.older_transfer_fee.transfer_fee_basis_points) != 0
-                    || <u16>::from(ext.newer_transfer_fee.transfer_fee_basis_points) != 0
-                {
-                    xmsg!("Transfer fee must be 0 for tokens, got: {:?}", ext);
-                    return err!(LimoError::UnsupportedTokenExtension);
-                }
+                transfer_fee_bps = std::cmp::max(
+                    <u16>::from(ext.older_transfer_fee.transfer_fee_basis_points),
+                    <u16>::from(ext.newer_transfer_fee.transfer_fee_basis_points),
+                );
             } else if mint_ext == ExtensionType::TransferHook {
                 let ext =
                     mint.get_extension::<spl_token_2022::extension::transfer_hook::TransferHook>()?;
                 let hook_program_id: Option<Pubkey> = ext.program_id.into();
-                if hook_program_id.is_some() {
+                if hook_program_id.is_some_and(|id| !transfer_hook_whitelist.contains(&id)) {
                     xmsg!(
-                        "Transfer hook program id must not be set for liquidity tokens, got {:?}",
+                        "Transfer hook program id must be unset or whitelisted for liquidity tokens, got {:?}",
                         ext
                     );
                     return err!(LimoError::UnsupportedTokenExtension);
@@ -222,7 +331,30 @@ pub mod token_2022 {
                 }
             }
         }
-        Ok(())
+        Ok(transfer_fee_bps)
+    }
+
+    pub fn transfer_fee_inclusive_amount(
+        mint_acc_info: &AccountInfo,
+        net_amount: u64,
+        epoch: Epoch,
+    ) -> anchor_lang::Result<u64> {
+        if mint_acc_info.owner == &spl_token::id() {
+            return Ok(net_amount);
+        }
+
+        let mint_data = mint_acc_info.data.borrow();
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let Ok(transfer_fee_config) =
+            mint.get_extension::<spl_token_2022::extension::transfer_fee::TransferFeeConfig>()
+        else {
+            return Ok(net_amount);
+        };
+
+        transfer_fee_config
+            .get_epoch_fee(epoch)
+            .calculate_pre_fee_amount(net_amount)
+            .ok_or_else(|| error!(LimoError::MathOverflow))
     }
 }
 
@@ -1,3 +1,42 @@
 pub const FULL_BPS: u64 = 10_000;
+pub const CURRENT_PROTOCOL_VERSION: u32 = 2;
 pub const UPDATE_GLOBAL_CONFIG_BYTE_SIZE: usize = 128;
-pub const USER_SWAP_BALANCE_STATE_SIZE: usize = 24;
+pub const USER_SWAP_BALANCE_STATE_SIZE: usize = 32;
+pub const VWAP_ORACLE_SIZE: usize = 224;
+pub const MINT_FEE_CONFIG_SIZE: usize = 136;
+pub const ORDER_BOOK_SNAPSHOT_SIZE: usize = 208;
+pub const MAKER_VOLUME_SIZE: usize = 192;
+pub const MAKER_ANALYTICS_SIZE: usize = 208;
+pub const MAX_BATCH_WITHDRAW_MAKER_TIPS_ORDERS: usize = 8;
+pub const MAX_BATCH_WITHDRAW_HOST_TIP_CONFIGS: usize = 8;
+pub const MAKER_ACTIVITY_SIZE: usize = 80;
+pub const MAKER_ORDER_COUNT_SIZE: usize = 4;
+pub const VAULT_ESCROW_SIZE: usize = 72;
+pub const DEFAULT_MAX_FLASH_INTERMEDIARY_IXS: u8 = 20;
+pub const MAX_LOG_PENDING_FILLS_ORDERS: usize = 20;
+pub const MAX_BULK_CLOSE_EMPTY_VAULTS: usize = 8;
+pub const MAX_TAKE_ORDER_BATCH_SIZE: usize = 8;
+pub const MAX_CANCEL_ORDERS_BATCH_SIZE: usize = 8;
+pub const MAX_UPDATE_GLOBAL_CONFIG_BATCH_SIZE: usize = 8;
+pub const UPDATE_GLOBAL_CONFIG_BATCH_VALUE_SIZE: usize = 32;
+pub const FEE_TIER_PREMIUM: u8 = 1;
+pub const HOST_FEE_ROUNDING_MODE_FLOOR: u8 = 1;
+pub const ORDER_SIZE: usize = 416;
+pub const ORDER_TRANSFER_PROPOSAL_SIZE: usize = 104;
+pub const MAKER_NONCE_TRACKER_SIZE: usize = 32;
+pub const ORDER_REFERRER_SIZE: usize = 96;
+pub const DUTCH_AUCTION_SIZE: usize = 96;
+pub const STOP_LIMIT_TRIGGER_SIZE: usize = 112;
+pub const ORDER_DELEGATE_SIZE: usize = 64;
+pub const MAX_ADDITIONAL_COUNTERPARTIES: usize = 3;
+pub const ORDER_COUNTERPARTIES_SIZE: usize = 128;
+pub const ORDER_OUTPUT_RECIPIENT_SIZE: usize = 64;
+pub const PAIR_ORACLE_CONFIG_SIZE: usize = 128;
+pub const ALLOWED_PROGRAM_SIZE: usize = 34;
+pub const MAX_MULTISIG_SIGNERS: usize = 5;
+pub const MAX_APPROVED_TRANSFER_HOOKS: usize = 4;
+pub const MAX_REGISTERED_SWAP_PROGRAMS: usize = 16;
+pub const SWAP_PROGRAM_REGISTRY_SIZE: usize = 544;
+pub const MULTISIG_ADMIN_SIZE: usize = 202;
+pub const PROPOSAL_SIZE: usize = 115;
+pub const ORACLE_MAX_PRICE_AGE_SECONDS: i64 = 60;
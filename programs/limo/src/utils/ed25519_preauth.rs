@@ -0,0 +1,260 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{ed25519_program, instruction::Instruction},
+};
+
+use super::flash_ixs::ix_utils;
+use crate::LimoError;
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+const PUBKEY_SIZE: usize = 32;
+
+pub fn create_order_preauth_message(
+    global_config: &Pubkey,
+    input_mint: &Pubkey,
+    output_mint: &Pubkey,
+    input_amount: u64,
+    output_amount: u64,
+    order_type: u8,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(3 * PUBKEY_SIZE + 8 + 8 + 1 + 8);
+    message.extend_from_slice(global_config.as_ref());
+    message.extend_from_slice(input_mint.as_ref());
+    message.extend_from_slice(output_mint.as_ref());
+    message.extend_from_slice(&input_amount.to_le_bytes());
+    message.extend_from_slice(&output_amount.to_le_bytes());
+    message.push(order_type);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+pub fn ensure_preceding_ed25519_ix_signed(
+    instruction_sysvar_account_info: &AccountInfo,
+    expected_message: &[u8],
+) -> Result<Pubkey> {
+    let instruction_loader = ix_utils::BpfInstructionLoader {
+        instruction_sysvar_account_info,
+    };
+    ensure_preceding_ed25519_ix_signed_internal(&instruction_loader, expected_message)
+}
+
+fn ensure_preceding_ed25519_ix_signed_internal(
+    instruction_loader: &impl ix_utils::InstructionLoader,
+    expected_message: &[u8],
+) -> Result<Pubkey> {
+    let current_idx: usize = instruction_loader.load_current_index()?.into();
+    let Some(ed25519_idx) = current_idx.checked_sub(1) else {
+        msg!("No instruction precedes the current one");
+        return err!(LimoError::InvalidEd25519Preauthorization);
+    };
+
+    let ed25519_ix = instruction_loader.load_instruction_at(ed25519_idx)?;
+    if ed25519_ix.program_id != ed25519_program::ID {
+        msg!("Preceding ix is not an ed25519 signature verification");
+        return err!(LimoError::InvalidEd25519Preauthorization);
+    }
+
+    let (signer, message) = parse_single_signature_ix(&ed25519_ix)?;
+    if message != expected_message {
+        msg!("Signed message does not match the order parameters");
+        return err!(LimoError::InvalidEd25519Preauthorization);
+    }
+
+    Ok(signer)
+}
+
+fn parse_single_signature_ix(ix: &Instruction) -> Result<(Pubkey, &[u8])> {
+    let data = &ix.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE || data[0] != 1 {
+        msg!("Expected exactly one ed25519 signature");
+        return err!(LimoError::InvalidEd25519Preauthorization);
+    }
+
+    let offsets = &data[SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE];
+    let read_u16 = |i: usize| usize::from(u16::from_le_bytes([offsets[i], offsets[i + 1]]));
+
+    let signature_ix_index = read_u16(2);
+    let public_key_offset = read_u16(4);
+    let public_key_ix_index = read_u16(6);
+    let message_offset = read_u16(8);
+    let message_size = read_u16(10);
+    let message_ix_index = read_u16(12);
+
+    // All parts must live in the ed25519 instruction itself, otherwise the
+    // verified bytes could come from an instruction we never look at
+    let this_ix = usize::from(u16::MAX);
+    if signature_ix_index != this_ix
+        || public_key_ix_index != this_ix
+        || message_ix_index != this_ix
+    {
+        msg!("Ed25519 signature data must be inlined");
+        return err!(LimoError::InvalidEd25519Preauthorization);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_SIZE)
+        .ok_or(LimoError::InvalidEd25519Preauthorization)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(LimoError::InvalidEd25519Preauthorization)?;
+
+    Ok((Pubkey::try_from(public_key).unwrap(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA_START: usize = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+    const SIGNATURE_SIZE: usize = 64;
+    const THIS_IX: u16 = u16::MAX;
+
+    struct MockInstructionLoader {
+        instructions: Vec<Instruction>,
+        current_index: u16,
+    }
+
+    impl ix_utils::InstructionLoader for MockInstructionLoader {
+        fn load_instruction_at(
+            &self,
+            index: usize,
+        ) -> std::result::Result<Instruction, ProgramError> {
+            self.instructions
+                .get(index)
+                .cloned()
+                .ok_or(ProgramError::InvalidArgument)
+        }
+
+        fn load_current_index(&self) -> std::result::Result<u16, ProgramError> {
+            Ok(self.current_index)
+        }
+    }
+
+    fn ed25519_ix_data(signer: &Pubkey, message: &[u8], ix_indexes: [u16; 3]) -> Vec<u8> {
+        let public_key_offset = DATA_START;
+        let signature_offset = public_key_offset + PUBKEY_SIZE;
+        let message_offset = signature_offset + SIGNATURE_SIZE;
+
+        let mut data = vec![1, 0];
+        for field in [
+            signature_offset as u16,
+            ix_indexes[0],
+            public_key_offset as u16,
+            ix_indexes[1],
+            message_offset as u16,
+            message.len() as u16,
+            ix_indexes[2],
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; SIGNATURE_SIZE]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn ix(program_id: Pubkey, data: Vec<u8>) -> Instruction {
+        Instruction {
+            program_id,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn run(preceding_ix: Instruction, expected_message: &[u8]) -> Result<Pubkey> {
+        let loader = MockInstructionLoader {
+            instructions: vec![preceding_ix, ix(crate::ID, vec![])],
+            current_index: 1,
+        };
+        ensure_preceding_ed25519_ix_signed_internal(&loader, expected_message)
+    }
+
+    #[test]
+    fn accepts_inlined_signature_over_expected_message() {
+        let signer = Pubkey::new_unique();
+        let data = ed25519_ix_data(&signer, b"order", [THIS_IX; 3]);
+
+        assert_eq!(run(ix(ed25519_program::ID, data), b"order"), Ok(signer));
+    }
+
+    #[test]
+    fn rejects_message_mismatch() {
+        let data = ed25519_ix_data(&Pubkey::new_unique(), b"order", [THIS_IX; 3]);
+
+        assert_eq!(
+            run(ix(ed25519_program::ID, data), b"other"),
+            Err(LimoError::InvalidEd25519Preauthorization.into())
+        );
+    }
+
+    #[test]
+    fn rejects_signature_parts_from_other_instructions() {
+        for not_inlined in 0..3 {
+            let mut ix_indexes = [THIS_IX; 3];
+            ix_indexes[not_inlined] = 1;
+            let data = ed25519_ix_data(&Pubkey::new_unique(), b"order", ix_indexes);
+
+            assert_eq!(
+                run(ix(ed25519_program::ID, data), b"order"),
+                Err(LimoError::InvalidEd25519Preauthorization.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_offsets() {
+        let mut data = ed25519_ix_data(&Pubkey::new_unique(), b"order", [THIS_IX; 3]);
+        data.truncate(data.len() - 1);
+        assert_eq!(
+            parse_single_signature_ix(&ix(ed25519_program::ID, data)).err(),
+            Some(LimoError::InvalidEd25519Preauthorization.into())
+        );
+
+        let mut data = ed25519_ix_data(&Pubkey::new_unique(), b"order", [THIS_IX; 3]);
+        let public_key_offset = SIGNATURE_OFFSETS_START + 4;
+        data[public_key_offset..public_key_offset + 2].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(
+            parse_single_signature_ix(&ix(ed25519_program::ID, data)).err(),
+            Some(LimoError::InvalidEd25519Preauthorization.into())
+        );
+
+        assert_eq!(
+            parse_single_signature_ix(&ix(ed25519_program::ID, vec![1, 0])).err(),
+            Some(LimoError::InvalidEd25519Preauthorization.into())
+        );
+    }
+
+    #[test]
+    fn rejects_signature_count_other_than_one() {
+        for num_signatures in [0, 2] {
+            let mut data = ed25519_ix_data(&Pubkey::new_unique(), b"order", [THIS_IX; 3]);
+            data[0] = num_signatures;
+
+            assert_eq!(
+                run(ix(ed25519_program::ID, data), b"order"),
+                Err(LimoError::InvalidEd25519Preauthorization.into())
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_preceding_ix_from_another_program() {
+        let data = ed25519_ix_data(&Pubkey::new_unique(), b"order", [THIS_IX; 3]);
+
+        assert_eq!(
+            run(ix(Pubkey::new_unique(), data), b"order"),
+            Err(LimoError::InvalidEd25519Preauthorization.into())
+        );
+
+        let loader = MockInstructionLoader {
+            instructions: vec![ix(crate::ID, vec![])],
+            current_index: 0,
+        };
+        assert_eq!(
+            ensure_preceding_ed25519_ix_signed_internal(&loader, b"order"),
+            Err(LimoError::InvalidEd25519Preauthorization.into())
+        );
+    }
+}
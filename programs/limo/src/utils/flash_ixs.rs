@@ -14,7 +14,10 @@ use anchor_spl::{
 };
 use solana_program::pubkey;
 
-use crate::LimoError;
+use crate::{
+    instruction::AssertPriceWithinBounds, seeds::ALLOWED_PROGRAM_SEED, state::AllowedProgram,
+    LimoError,
+};
 
 const COMPUTE_BUDGET_PUBKEY: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
 
@@ -22,6 +25,9 @@ pub fn ensure_second_ix_match<T>(
     instruction_sysvar_account_info: &AccountInfo,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<T>
 where
     T: Discriminator + AnchorDeserialize,
@@ -29,19 +35,37 @@ where
     let instruction_loader = ix_utils::BpfInstructionLoader {
         instruction_sysvar_account_info,
     };
-    ensure_second_ix_match_internal(&instruction_loader, input_mint, output_mint)
+    ensure_second_ix_match_internal(
+        &instruction_loader,
+        input_mint,
+        output_mint,
+        max_intermediary_ixs,
+        allowed_programs,
+        restrict_intermediary_ixs,
+    )
 }
 
 fn ensure_second_ix_match_internal<T>(
     instruction_loader: &impl ix_utils::InstructionLoader,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<T>
 where
     T: Discriminator + AnchorDeserialize,
 {
     let current_idx = instruction_loader.load_current_index()?.into();
-    let second_ix = search_second_ix(current_idx, instruction_loader, input_mint, output_mint)?;
+    let second_ix = search_second_ix(
+        current_idx,
+        instruction_loader,
+        input_mint,
+        output_mint,
+        max_intermediary_ixs,
+        allowed_programs,
+        restrict_intermediary_ixs,
+    )?;
     if let Some(discriminator) = second_ix.data.get(..8) {
         if discriminator != T::discriminator() {
             msg!("Extra ix is not the expected one");
@@ -63,12 +87,15 @@ fn search_second_ix(
     instruction_loader: &impl ix_utils::InstructionLoader,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<Instruction> {
     for idx in 0..current_idx {
         let ix = instruction_loader.load_instruction_at(idx)?;
 
         require!(
-            program_id_allowed(ix.program_id),
+            ix_allowed(&ix, allowed_programs),
             LimoError::FlashTxWithUnexpectedIxs
         );
 
@@ -78,6 +105,7 @@ fn search_second_ix(
     }
 
     let mut found_extra_ix = None;
+    let mut intermediary_ixs_count: usize = 0;
     let mut ix_iterator =
         ix_utils::IxIterator::new_at(current_idx.checked_add(1).unwrap(), instruction_loader);
 
@@ -87,10 +115,19 @@ fn search_second_ix(
             return Err(error.into());
         }
         let ix = ix?;
-        if ix.program_id == crate::id() {
+        if ix.program_id == crate::id() && !is_price_guard_ix(&ix) {
             found_extra_ix = Some(ix);
             break;
         }
+        intermediary_ixs_count += 1;
+        require!(
+            intermediary_ixs_count <= usize::from(max_intermediary_ixs),
+            LimoError::FlashTxTooComplex
+        );
+        require!(
+            !restrict_intermediary_ixs || ix_allowed(&ix, allowed_programs),
+            LimoError::FlashTxWithUnexpectedIxs
+        );
     }
 
     let extra_ix = found_extra_ix.ok_or_else(|| error!(LimoError::FlashIxsNotEnded))?;
@@ -102,7 +139,7 @@ fn search_second_ix(
         }
         let ix = ix?;
         require!(
-            program_id_allowed(ix.program_id),
+            ix_allowed(&ix, allowed_programs),
             LimoError::FlashTxWithUnexpectedIxs
         );
         if ix.program_id == token_2022::ID {
@@ -113,17 +150,71 @@ fn search_second_ix(
     Ok(extra_ix)
 }
 
-fn program_id_allowed(program_id: Pubkey) -> bool {
+fn ix_allowed(ix: &Instruction, allowed_programs: &[Pubkey]) -> bool {
+    program_id_allowed(ix.program_id, allowed_programs) || is_price_guard_ix(ix)
+}
+
+fn is_price_guard_ix(ix: &Instruction) -> bool {
+    ix.program_id == crate::id()
+        && ix.data.get(..8) == Some(&AssertPriceWithinBounds::discriminator()[..])
+}
+
+fn program_id_allowed(program_id: Pubkey, allowed_programs: &[Pubkey]) -> bool {
     program_id == COMPUTE_BUDGET_PUBKEY
         || program_id == spl_token::ID
         || program_id == token_2022::ID
         || program_id == associated_token::ID
+        || allowed_programs.contains(&program_id)
+}
+
+pub fn get_registered_programs(
+    global_config: &Pubkey,
+    allowed_program_accounts: &[AccountInfo],
+) -> Vec<Pubkey> {
+    allowed_program_accounts
+        .iter()
+        .filter(|account| account.owner == &crate::id())
+        .filter_map(|account| {
+            let data = account.try_borrow_data().ok()?;
+            let allowed_program = AllowedProgram::try_deserialize(&mut &data[..]).ok()?;
+            let address = Pubkey::create_program_address(
+                &[
+                    ALLOWED_PROGRAM_SEED,
+                    global_config.as_ref(),
+                    allowed_program.program_id.as_ref(),
+                    &[allowed_program.bump],
+                ],
+                &crate::id(),
+            )
+            .ok()?;
+            (address == account.key() && allowed_program.enabled != 0)
+                .then_some(allowed_program.program_id)
+        })
+        .collect()
+}
+
+pub fn split_allowed_program_accounts<'a, 'info>(
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    let position = remaining_accounts
+        .iter()
+        .position(|account| {
+            account.owner == &crate::id()
+                && account
+                    .try_borrow_data()
+                    .is_ok_and(|data| data.get(..8) == Some(&AllowedProgram::discriminator()[..]))
+        })
+        .unwrap_or(remaining_accounts.len());
+    remaining_accounts.split_at(position)
 }
 
 pub fn ensure_first_ix_match<T>(
     instruction_sysvar_account_info: &AccountInfo,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<T>
 where
     T: Discriminator + AnchorDeserialize,
@@ -131,19 +222,37 @@ where
     let instruction_loader = ix_utils::BpfInstructionLoader {
         instruction_sysvar_account_info,
     };
-    ensure_first_ix_match_internal(&instruction_loader, input_mint, output_mint)
+    ensure_first_ix_match_internal(
+        &instruction_loader,
+        input_mint,
+        output_mint,
+        max_intermediary_ixs,
+        allowed_programs,
+        restrict_intermediary_ixs,
+    )
 }
 
 fn ensure_first_ix_match_internal<T>(
     instruction_loader: &impl ix_utils::InstructionLoader,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<T>
 where
     T: Discriminator + AnchorDeserialize,
 {
     let current_idx = instruction_loader.load_current_index()?.into();
-    let first_ix = search_first_ix(current_idx, instruction_loader, input_mint, output_mint)?;
+    let first_ix = search_first_ix(
+        current_idx,
+        instruction_loader,
+        input_mint,
+        output_mint,
+        max_intermediary_ixs,
+        allowed_programs,
+        restrict_intermediary_ixs,
+    )?;
     if let Some(discriminator) = first_ix.data.get(..8) {
         if discriminator != T::discriminator() {
             msg!("Extra ix is not the expected one");
@@ -165,6 +274,9 @@ fn search_first_ix(
     instruction_loader: &impl ix_utils::InstructionLoader,
     input_mint: &Pubkey,
     output_mint: &Pubkey,
+    max_intermediary_ixs: u8,
+    allowed_programs: &[Pubkey],
+    restrict_intermediary_ixs: bool,
 ) -> Result<Instruction> {
     let mut ix_iterator =
         ix_utils::IxIterator::new_at(current_idx.checked_add(1).unwrap(), instruction_loader);
@@ -176,7 +288,7 @@ fn search_first_ix(
         }
         let ix = ix?;
         require!(
-            program_id_allowed(ix.program_id),
+            ix_allowed(&ix, allowed_programs),
             LimoError::FlashTxWithUnexpectedIxs
         );
         if ix.program_id == token_2022::ID {
@@ -188,12 +300,27 @@ fn search_first_ix(
 
     for idx in 0..current_idx {
         let ix = instruction_loader.load_instruction_at(idx)?;
-        if ix.program_id == crate::id() {
+        if ix.program_id == crate::id() && !is_price_guard_ix(&ix) {
+            let intermediary_ixs_count = current_idx - idx - 1;
+            require!(
+                intermediary_ixs_count <= usize::from(max_intermediary_ixs),
+                LimoError::FlashTxTooComplex
+            );
+            if restrict_intermediary_ixs {
+                for intermediary_idx in idx + 1..current_idx {
+                    let intermediary_ix =
+                        instruction_loader.load_instruction_at(intermediary_idx)?;
+                    require!(
+                        ix_allowed(&intermediary_ix, allowed_programs),
+                        LimoError::FlashTxWithUnexpectedIxs
+                    );
+                }
+            }
             found_extra_ix = Some(ix);
             break;
         } else {
             require!(
-                program_id_allowed(ix.program_id),
+                ix_allowed(&ix, allowed_programs),
                 LimoError::FlashTxWithUnexpectedIxs
             );
             if ix.program_id == token_2022::ID {
@@ -325,7 +452,8 @@ pub mod ix_utils {
     }
 
     pub struct IxIterator<'a, IxLoader: InstructionLoader> {
-        current_ix: usize,
+        current_ix: Option<usize>,
+        backward: bool,
         instruction_loader: &'a IxLoader,
     }
 
@@ -335,7 +463,17 @@ pub mod ix_utils {
     {
         pub fn new_at(start_ix_index: usize, instruction_loader: &'a IxLoader) -> Self {
             Self {
-                current_ix: start_ix_index,
+                current_ix: Some(start_ix_index),
+                backward: false,
+                instruction_loader,
+            }
+        }
+
+        /// Iterates from `start_from` down to index 0, inclusive
+        pub fn new_backward(start_from: usize, instruction_loader: &'a IxLoader) -> Self {
+            Self {
+                current_ix: Some(start_from),
+                backward: true,
                 instruction_loader,
             }
         }
@@ -348,9 +486,14 @@ pub mod ix_utils {
         type Item = std::result::Result<Instruction, ProgramError>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            match self.instruction_loader.load_instruction_at(self.current_ix) {
+            let current_ix = self.current_ix?;
+            match self.instruction_loader.load_instruction_at(current_ix) {
                 Ok(ix) => {
-                    self.current_ix = self.current_ix.checked_add(1).unwrap();
+                    self.current_ix = if self.backward {
+                        current_ix.checked_sub(1)
+                    } else {
+                        Some(current_ix.checked_add(1).unwrap())
+                    };
                     Some(Ok(ix))
                 }
                 Err(ProgramError::InvalidArgument) => None,
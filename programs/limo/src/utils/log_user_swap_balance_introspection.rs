@@ -106,10 +106,15 @@ fn search_start_ix(
     instruction_loader: &impl ix_utils::InstructionLoader,
 ) -> Result<Instruction> {
     let mut found_start_ix = None;
+    let Some(start_from) = current_idx.checked_sub(1) else {
+        return err!(LimoError::FlashIxsNotStarted);
+    };
 
-    for idx in (0..current_idx).rev() {
-        let ix = instruction_loader.load_instruction_at(idx)?;
-        msg!("ix {} ix program: {:?}", idx, ix.program_id);
+    for (offset, ix) in
+        ix_utils::IxIterator::new_backward(start_from, instruction_loader).enumerate()
+    {
+        let ix = ix?;
+        msg!("ix {} ix program: {:?}", start_from - offset, ix.program_id);
         if ix.program_id == crate::id() {
             found_start_ix = Some(ix);
             break;
@@ -87,3 +87,26 @@ macro_rules! require_lte {
         }
     };
 }
+
+#[macro_export]
+macro_rules! check_cpi_not_allowed {
+    ($ctx:expr) => {{
+        let instruction_sysvar_account = $ctx.accounts.sysvar_instructions.to_account_info();
+        let current_ix_program_id =
+            anchor_lang::solana_program::sysvar::instructions::get_instruction_relative(
+                0,
+                &instruction_sysvar_account,
+            )?
+            .program_id;
+        require_keys_eq!(
+            current_ix_program_id,
+            $crate::ID,
+            $crate::LimoError::CPINotAllowed
+        );
+        require!(
+            anchor_lang::solana_program::instruction::get_stack_height()
+                <= anchor_lang::solana_program::instruction::TRANSACTION_LEVEL_STACK_HEIGHT,
+            $crate::LimoError::CPINotAllowed
+        );
+    }};
+}
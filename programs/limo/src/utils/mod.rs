@@ -1,7 +1,9 @@
 pub mod assert_user_swap_balance_introspection;
 pub mod constraints;
 pub mod consts;
+pub mod ed25519_preauth;
 pub mod flash_ixs;
 pub mod fraction;
 pub mod log_user_swap_balance_introspection;
 pub mod macros;
+pub mod oracle;
@@ -0,0 +1,247 @@
+use anchor_lang::{prelude::*, solana_program::pubkey};
+
+use crate::{
+    state::PairOracleConfig,
+    utils::{
+        consts::{FULL_BPS, ORACLE_MAX_PRICE_AGE_SECONDS},
+        fraction::U256,
+    },
+    LimoError,
+};
+
+const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub feed_id: [u8; 32],
+    pub price: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+pub fn get_pyth_price_checked(
+    price_account: &AccountInfo,
+    current_timestamp: i64,
+) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *price_account.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        LimoError::InvalidOraclePriceAccount
+    );
+
+    let data = price_account.try_borrow_data()?;
+    require!(
+        data.len() >= 101 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        LimoError::InvalidOraclePriceAccount
+    );
+    // Layout: discriminator, write_authority, verification_level, then the
+    // price message (feed_id, price, conf, exponent, publish_time, ...).
+    // Only fully verified updates carry no extra verification_level bytes.
+    require!(
+        data[40] == VERIFICATION_LEVEL_FULL,
+        LimoError::InvalidOraclePriceAccount
+    );
+
+    let feed_id: [u8; 32] = data[41..73].try_into().unwrap();
+    let price = i64::from_le_bytes(data[73..81].try_into().unwrap());
+    let exponent = i32::from_le_bytes(data[89..93].try_into().unwrap());
+    let publish_time = i64::from_le_bytes(data[93..101].try_into().unwrap());
+
+    let price = u64::try_from(price).map_err(|_| LimoError::InvalidOraclePriceAccount)?;
+    require!(price > 0, LimoError::InvalidOraclePriceAccount);
+
+    let age = current_timestamp.saturating_sub(publish_time);
+    if age > ORACLE_MAX_PRICE_AGE_SECONDS {
+        msg!(
+            "Oracle price is stale publish_time={} now={}",
+            publish_time,
+            current_timestamp
+        );
+        return err!(LimoError::InvalidOraclePriceAccount);
+    }
+
+    Ok(OraclePrice {
+        feed_id,
+        price,
+        exponent,
+        publish_time,
+    })
+}
+
+pub fn get_optional_pyth_price_checked(
+    price_account: Option<&AccountInfo>,
+    expected_price_feed: Option<&Pubkey>,
+    current_timestamp: i64,
+) -> Result<Option<OraclePrice>> {
+    if let (Some(price_account), Some(expected_price_feed)) = (price_account, expected_price_feed) {
+        require_keys_eq!(
+            price_account.key(),
+            *expected_price_feed,
+            LimoError::InvalidOraclePriceAccount
+        );
+    }
+
+    price_account
+        .map(|price_account| get_pyth_price_checked(price_account, current_timestamp))
+        .transpose()
+}
+
+/// While oracle price bounds are configured every fill needs a price from the
+/// feed the admin configured for the pair, a taker picked feed is rejected.
+pub fn check_oracle_feed_for_pair(
+    oracle_price: Option<&OraclePrice>,
+    pair_oracle_config: Option<&PairOracleConfig>,
+    tolerance_bps: u16,
+) -> Result<()> {
+    if tolerance_bps == 0 {
+        return Ok(());
+    }
+
+    let pair_oracle_config =
+        pair_oracle_config.ok_or_else(|| error!(LimoError::OraclePriceFeedNotConfigured))?;
+    let oracle_price = oracle_price.ok_or_else(|| error!(LimoError::OraclePriceAccountRequired))?;
+    require!(
+        oracle_price.feed_id == pair_oracle_config.price_feed_id,
+        LimoError::InvalidOraclePriceAccount
+    );
+
+    Ok(())
+}
+
+pub fn is_oracle_price_at_or_above(
+    oracle_price: &OraclePrice,
+    price_numerator: u64,
+    price_denominator: u64,
+) -> Result<bool> {
+    require!(price_denominator > 0, LimoError::InvalidParameterType);
+
+    let exponent_scale = U256::from(10u64)
+        .checked_pow(U256::from(oracle_price.exponent.unsigned_abs()))
+        .ok_or(LimoError::MathOverflow)?;
+
+    // oracle price * 10^exponent >= numerator / denominator
+    let mut oracle_side = U256::from(oracle_price.price)
+        .checked_mul(U256::from(price_denominator))
+        .ok_or(LimoError::MathOverflow)?;
+    let mut trigger_side = U256::from(price_numerator);
+    if oracle_price.exponent < 0 {
+        trigger_side = trigger_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    } else {
+        oracle_side = oracle_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    }
+
+    Ok(oracle_side >= trigger_side)
+}
+
+pub fn check_fill_price_within_oracle_bounds(
+    oracle_price: &OraclePrice,
+    input_amount: u64,
+    input_decimals: u8,
+    output_amount: u64,
+    output_decimals: u8,
+    tolerance_bps: u16,
+) -> Result<()> {
+    require!(input_amount > 0, LimoError::InvalidOraclePriceAccount);
+
+    let pow10 = |exp: u32| U256::from(10u64).checked_pow(U256::from(exp));
+    let exponent_scale =
+        pow10(oracle_price.exponent.unsigned_abs()).ok_or(LimoError::MathOverflow)?;
+
+    // fill price = (output / 10^output_decimals) / (input / 10^input_decimals)
+    // oracle price = price * 10^exponent
+    let mut fill_side = pow10(input_decimals.into())
+        .and_then(|scale| scale.checked_mul(U256::from(output_amount)))
+        .ok_or(LimoError::MathOverflow)?;
+    let mut oracle_side = pow10(output_decimals.into())
+        .and_then(|scale| scale.checked_mul(U256::from(input_amount)))
+        .and_then(|value| value.checked_mul(U256::from(oracle_price.price)))
+        .ok_or(LimoError::MathOverflow)?;
+    if oracle_price.exponent < 0 {
+        fill_side = fill_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    } else {
+        oracle_side = oracle_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    }
+
+    if !is_within_tolerance(fill_side, oracle_side, tolerance_bps) {
+        msg!(
+            "Fill price out of oracle bounds input={} output={} oracle_price={} exponent={} tolerance_bps={}",
+            input_amount,
+            output_amount,
+            oracle_price.price,
+            oracle_price.exponent,
+            tolerance_bps
+        );
+        return err!(LimoError::OraclePriceBoundExceeded);
+    }
+
+    Ok(())
+}
+
+pub fn check_expected_price_within_oracle_bounds(
+    oracle_price: &OraclePrice,
+    expected_price_numerator: u64,
+    expected_price_denominator: u64,
+    tolerance_bps: u16,
+) -> Result<()> {
+    require!(
+        expected_price_denominator > 0,
+        LimoError::InvalidOraclePriceAccount
+    );
+
+    let exponent_scale = U256::from(10u64)
+        .checked_pow(U256::from(oracle_price.exponent.unsigned_abs()))
+        .ok_or(LimoError::MathOverflow)?;
+
+    // expected price = numerator / denominator
+    // oracle price = price * 10^exponent
+    let mut expected_side = U256::from(expected_price_numerator);
+    let mut oracle_side = U256::from(oracle_price.price) * U256::from(expected_price_denominator);
+    if oracle_price.exponent < 0 {
+        expected_side = expected_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    } else {
+        oracle_side = oracle_side
+            .checked_mul(exponent_scale)
+            .ok_or(LimoError::MathOverflow)?;
+    }
+
+    if !is_within_tolerance(expected_side, oracle_side, tolerance_bps) {
+        msg!(
+            "Expected price out of oracle bounds numerator={} denominator={} oracle_price={} exponent={} tolerance_bps={}",
+            expected_price_numerator,
+            expected_price_denominator,
+            oracle_price.price,
+            oracle_price.exponent,
+            tolerance_bps
+        );
+        return err!(LimoError::OraclePriceBoundExceeded);
+    }
+
+    Ok(())
+}
+
+fn is_within_tolerance(value: U256, reference: U256, tolerance_bps: u16) -> bool {
+    let diff = if value > reference {
+        value - reference
+    } else {
+        reference - value
+    };
+    match (
+        diff.checked_mul(U256::from(FULL_BPS)),
+        reference.checked_mul(U256::from(tolerance_bps)),
+    ) {
+        (Some(lhs), Some(rhs)) => lhs <= rhs,
+        _ => false,
+    }
+}